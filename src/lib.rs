@@ -1,26 +1,34 @@
-#![feature(type_alias_impl_trait)]
-/// A library which provide similar functionality of rxjs in rust.
-/// See usage in https://rxjs-dev.firebaseapp.com/guide/operators
-/// It supports most of the operators in rxjs. But due to language difference,
-/// a couple of things need to notice
-/// 0. Observables are implemented as Stream in rust. Currently I'm using
-/// Stream in futures 0.1. And many operators are just alias of the ones in 
-/// Stream library.
-/// 1. Creation operators in rxjs doc is put to module source.
-/// 2. Ajax and event based creation are not supported
-/// 3. For combining operators such as merge and concat, currently this 
-/// library only support two operands. I'm still considering how to support 
-/// random number of operands. There are many ways to do so, but none is 
-/// perfect. One way is to use Vec, which requires all operands are with 
-/// same type. Another way is to use Vec<Box<>>, which requires allocation.
-/// A third way is to use hlist with some macro. The last one is most 
-/// promising one, but I'm still evaluating. 
-/// One way to mitigate the problem is to do the operation multiple times. 
-/// Say merge(merge(s1, s2), s3) etc. 
-
+//! A library which provide similar functionality of rxjs in rust.
+//! See usage in https://rxjs-dev.firebaseapp.com/guide/operators
+//! It supports most of the operators in rxjs. But due to language difference,
+//! a couple of things need to notice
+//! 0. Observables are implemented as Stream in rust. Currently I'm using
+//!    Stream in futures 0.1. And many operators are just alias of the ones in 
+//!    Stream library.
+//! 1. Creation operators in rxjs doc is put to module source.
+//! 2. Ajax and event based creation are not supported
+//! 3. For combining operators such as merge and concat, the plain version 
+//!    takes two operands. combine_latest, fork_join, merge, race and 
+//!    with_latest_from also come with numbered versions, say combine_latest3, 
+//!    taking up to 12 operands of different types and yielding flat tuples. 
+//!    For any number of operands of the same type, use the _vec versions.
+//! 4. Time based sources and operators read the time from a Scheduler, see
+//!    module scheduler. The default one is the real clock, driven by tokio 1.x,
+//!    or by futures-timer with the "futures-timer" feature and default features
+//!    off, so no particular executor is needed. Tests can use
+//!    VirtualTimeScheduler to control the time. Module testing builds
+//!    marble tests on top of it.
+//! 5. Errors are not a separate channel of Stream. Streams of Result get the
+//!    try_ operators of RxTryStreamEx, where the first Err ends the stream
+//!    as an error notification does in rxjs.
+//! 6. Subjects are in module subject. A subject is the sink side, and each
+//!    subscribe gives a new Stream.
 
 #[macro_use]
 extern crate futures;
 
+pub mod scheduler;
 pub mod source;
-pub mod operators;
\ No newline at end of file
+pub mod operators;
+pub mod subject;
+pub mod testing;
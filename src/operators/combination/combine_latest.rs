@@ -4,6 +4,7 @@ use futures::StreamExt;
 use futures::task::{Context, Poll};
 use pin_project::{pin_project};
 use core::pin::Pin;
+use std::fmt;
 
 #[pin_project(project = CombineLatestProj)]
 #[derive(Debug)]
@@ -96,13 +97,16 @@ impl<S1, S2> Stream for CombineLatest<S1, S2>
                     queued2.clone().unwrap()
                 );
                 Poll::Ready(Some(pair))
-        } else if s1.is_done() && s2.is_done() {
-            Poll::Ready(None)
-        } else if s1.is_done() && queued1.is_none() ||
+        } else if s1.is_done() && s2.is_done() ||
+            s1.is_done() && queued1.is_none() ||
             s2.is_done() && queued2.is_none() 
         {
             Poll::Ready(None)
         } else {
+            if s1_ready || s2_ready {
+                // The ready stream hasn't registered the waker, poll it again
+                cx.waker().wake_by_ref();
+            }
             Poll::Pending
         }
     }
@@ -158,6 +162,7 @@ impl<S> Stream for CombineLatestVec<S>
     ) -> Poll<Option<Self::Item>> {
         let CombineLatestVecProj { s_list, queued_list } = self.project();
         let mut done_count = 0;
+        let mut any_new = false;
         let len = s_list.len();
         for (i, s) in s_list.iter_mut().enumerate() {
             let has_new = match unsafe{Pin::new_unchecked(s)}.poll_next(cx) {
@@ -167,7 +172,7 @@ impl<S> Stream for CombineLatestVec<S>
                 }
                 Poll::Ready(None) => {
                     done_count+= 1; 
-                    if !queued_list[i].is_some() {
+                    if queued_list[i].is_none() {
                         // if some stream end but has not yield any value, the entier result done
                         return Poll::Ready(None)
                     }
@@ -177,24 +182,214 @@ impl<S> Stream for CombineLatestVec<S>
             };
             // If anyone ready
             if has_new {
-                let mut r = Vec::with_capacity(len);
-                for q in queued_list {
-                    if q.is_some() {
-                        r.push(q.clone().unwrap());
-                    } else {
-                        // If any queued item is not filled, the stream is not ready
-                        return Poll::Pending;
-                    }
+                // If any queued item is not filled, the stream is not ready,
+                // keep polling the rest so they register the waker
+                if queued_list.iter().all(|q| q.is_some()) {
+                    return Poll::Ready(Some(queued_list.iter().cloned().map(Option::unwrap).collect()));
                 }
-                return Poll::Ready(Some(r));
+                any_new = true;
             }
         }
-        // Not a single has_new
         if done_count == len {
             // everyone is done 
             Poll::Ready(None)
         } else {
+            if any_new {
+                // The ready streams haven't registered the waker, poll them again
+                cx.waker().wake_by_ref();
+            }
+            Poll::Pending
+        }
+    }
+}
+
+#[pin_project(project = CombineAllProj)]
+#[must_use = "streams do nothing unless polled"]
+pub struct CombineAll<S>
+    where S: Stream, S::Item: Stream, <S::Item as Stream>::Item: Clone
+{
+    #[pin]
+    s: Fuse<S>,
+    // Boxed, so adding inner streams doesn't move the pinned ones
+    inners: Vec<Pin<Box<Fuse<S::Item>>>>,
+    queued_list: Vec<Option<<S::Item as Stream>::Item>>,
+}
+
+// Derived Debug would not require the values of the inner streams to be Debug
+impl<S> fmt::Debug for CombineAll<S>
+    where S: Stream + fmt::Debug, S::Item: Stream + fmt::Debug,
+          <S::Item as Stream>::Item: Clone + fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CombineAll")
+            .field("s", &self.s)
+            .field("inners", &self.inners)
+            .field("queued_list", &self.queued_list)
+            .finish()
+    }
+}
+
+/// Combine the inner streams as they come from the outer stream. Once an
+/// inner stream is added, nothing is yielded until it yields its first value,
+/// and then the vectors have one more value. Same rules as combine_latest
+/// apply, and it ends when the outer stream and all the inner streams end.
+pub fn combine_all<S>(s: S) -> CombineAll<S>
+    where S: Stream, S::Item: Stream, <S::Item as Stream>::Item: Clone
+{
+    CombineAll {
+        s: s.fuse(),
+        inners: Vec::new(),
+        queued_list: Vec::new(),
+    }
+}
+
+impl<S> Stream for CombineAll<S>
+    where S: Stream, S::Item: Stream, <S::Item as Stream>::Item: Clone
+{
+    type Item = Vec<<S::Item as Stream>::Item>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let CombineAllProj { mut s, inners, queued_list } = self.project();
+        // One inner stream per call, the outer stream may never end
+        let outer_ready = match s.as_mut().poll_next(cx) {
+            Poll::Ready(Some(inner)) => {
+                inners.push(Box::pin(inner.fuse()));
+                queued_list.push(None);
+                true
+            }
+            Poll::Ready(None) | Poll::Pending => false,
+        };
+        let mut any_new = false;
+        for (inner, queued) in inners.iter_mut().zip(queued_list.iter_mut()) {
+            match inner.as_mut().poll_next(cx) {
+                Poll::Ready(Some(item)) => {
+                    *queued = Some(item);
+                    any_new = true;
+                }
+                // if some stream end but has not yield any value, the entier result done
+                Poll::Ready(None) if queued.is_none() => return Poll::Ready(None),
+                _ => (),
+            }
+        }
+        if any_new && queued_list.iter().all(|q| q.is_some()) {
+            Poll::Ready(Some(queued_list.iter().cloned().map(Option::unwrap).collect()))
+        } else if s.is_done() && inners.iter().all(|inner| inner.is_done()) {
+            Poll::Ready(None)
+        } else {
+            if outer_ready || any_new {
+                // The ready streams haven't registered the waker, poll them again
+                cx.waker().wake_by_ref();
+            }
             Poll::Pending
         }
     }
-}
\ No newline at end of file
+}
+
+/// Generate CombineLatestN for N heterogeneous streams yielding a flat tuple.
+/// It follows the same rules as the two streams version above.
+macro_rules! combine_latest_n {
+    ($name:ident, $proj:ident, $fn_name:ident, $(($S:ident, $s:ident, $queued:ident)),+) => {
+        #[pin_project(project = $proj)]
+        #[derive(Debug)]
+        #[must_use = "streams do nothing unless polled"]
+        pub struct $name<$($S),+>
+            where $($S: Stream, $S::Item: Clone),+
+        {
+            $(
+                #[pin]
+                $s: Fuse<$S>,
+            )+
+            $(
+                $queued: Option<$S::Item>,
+            )+
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fn_name<$($S),+>($($s: $S),+) -> $name<$($S),+>
+            where $($S: Stream, $S::Item: Clone),+
+        {
+            $name::new($($s),+)
+        }
+
+        impl<$($S),+> $name<$($S),+>
+            where $($S: Stream, $S::Item: Clone),+
+        {
+            #[allow(clippy::too_many_arguments)]
+            pub fn new($($s: $S),+) -> $name<$($S),+> {
+                $name {
+                    $($s: $s.fuse(),)+
+                    $($queued: None,)+
+                }
+            }
+        }
+
+        impl<$($S),+> Stream for $name<$($S),+>
+            where $($S: Stream, $S::Item: Clone),+
+        {
+            type Item = ($($S::Item),+);
+
+            fn poll_next(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                let $proj { $(mut $s,)+ $($queued,)+ } = self.project();
+                let mut any_ready = false;
+                $(
+                    if let Poll::Ready(Some(item)) = $s.as_mut().poll_next(cx) {
+                        *$queued = Some(item);
+                        any_ready = true;
+                    }
+                )+
+                if any_ready $(&& $queued.is_some())+ {
+                    Poll::Ready(Some(($($queued.clone().unwrap()),+)))
+                } else if true $(&& $s.is_done())+ ||
+                    false $(|| $s.is_done() && $queued.is_none())+
+                {
+                    Poll::Ready(None)
+                } else {
+                    if any_ready {
+                        // The ready streams haven't registered the waker, poll them again
+                        cx.waker().wake_by_ref();
+                    }
+                    Poll::Pending
+                }
+            }
+        }
+    };
+}
+
+combine_latest_n!(CombineLatest3, CombineLatest3Proj, combine_latest3,
+    (S1, s1, queued1), (S2, s2, queued2), (S3, s3, queued3));
+combine_latest_n!(CombineLatest4, CombineLatest4Proj, combine_latest4,
+    (S1, s1, queued1), (S2, s2, queued2), (S3, s3, queued3), (S4, s4, queued4));
+combine_latest_n!(CombineLatest5, CombineLatest5Proj, combine_latest5,
+    (S1, s1, queued1), (S2, s2, queued2), (S3, s3, queued3), (S4, s4, queued4),
+    (S5, s5, queued5));
+combine_latest_n!(CombineLatest6, CombineLatest6Proj, combine_latest6,
+    (S1, s1, queued1), (S2, s2, queued2), (S3, s3, queued3), (S4, s4, queued4),
+    (S5, s5, queued5), (S6, s6, queued6));
+combine_latest_n!(CombineLatest7, CombineLatest7Proj, combine_latest7,
+    (S1, s1, queued1), (S2, s2, queued2), (S3, s3, queued3), (S4, s4, queued4),
+    (S5, s5, queued5), (S6, s6, queued6), (S7, s7, queued7));
+combine_latest_n!(CombineLatest8, CombineLatest8Proj, combine_latest8,
+    (S1, s1, queued1), (S2, s2, queued2), (S3, s3, queued3), (S4, s4, queued4),
+    (S5, s5, queued5), (S6, s6, queued6), (S7, s7, queued7), (S8, s8, queued8));
+combine_latest_n!(CombineLatest9, CombineLatest9Proj, combine_latest9,
+    (S1, s1, queued1), (S2, s2, queued2), (S3, s3, queued3), (S4, s4, queued4),
+    (S5, s5, queued5), (S6, s6, queued6), (S7, s7, queued7), (S8, s8, queued8),
+    (S9, s9, queued9));
+combine_latest_n!(CombineLatest10, CombineLatest10Proj, combine_latest10,
+    (S1, s1, queued1), (S2, s2, queued2), (S3, s3, queued3), (S4, s4, queued4),
+    (S5, s5, queued5), (S6, s6, queued6), (S7, s7, queued7), (S8, s8, queued8),
+    (S9, s9, queued9), (S10, s10, queued10));
+combine_latest_n!(CombineLatest11, CombineLatest11Proj, combine_latest11,
+    (S1, s1, queued1), (S2, s2, queued2), (S3, s3, queued3), (S4, s4, queued4),
+    (S5, s5, queued5), (S6, s6, queued6), (S7, s7, queued7), (S8, s8, queued8),
+    (S9, s9, queued9), (S10, s10, queued10), (S11, s11, queued11));
+combine_latest_n!(CombineLatest12, CombineLatest12Proj, combine_latest12,
+    (S1, s1, queued1), (S2, s2, queued2), (S3, s3, queued3), (S4, s4, queued4),
+    (S5, s5, queued5), (S6, s6, queued6), (S7, s7, queued7), (S8, s8, queued8),
+    (S9, s9, queued9), (S10, s10, queued10), (S11, s11, queued11), (S12, s12, queued12));
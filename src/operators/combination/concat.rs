@@ -2,8 +2,10 @@ use futures::task::Poll;
 use futures::task::Context;
 use std::pin::Pin;
 use futures::stream::Chain;
-use futures::{StreamExt, FutureExt, Stream};
+use futures::{StreamExt, Stream};
 use pin_project::pin_project;
+use std::convert::identity;
+use super::super::transform::merge_map::MergeMap;
 
 pub fn concat<S1: Stream, S2: Stream<Item=S1::Item>>(s1: S1, s2: S2) -> Chain<S1, S2> {
     s1.chain(s2)
@@ -18,15 +20,15 @@ pub struct ChainVec<S: Stream> {
 }
 
 pub fn concat_vec<S: Stream>(s_vec: Vec<S>) -> ChainVec<S> {
-    ChainVec { s_vec: s_vec, current: 0}    
+    ChainVec { s_vec, current: 0}    
 }
 
-pub fn concat_all<SInner: Stream, SOuter: Stream<Item=SInner>>(s: SOuter) -> 
-    impl Stream<Item=SInner::Item> 
-{
-    s.collect()
-        .map(|streams| concat_vec(streams))
-        .flatten_stream() 
+pub type ConcatAll<S> = MergeMap<S, fn(<S as Stream>::Item) -> <S as Stream>::Item, <S as Stream>::Item>;
+
+/// The first inner stream is run as soon as it comes, and the later ones
+/// are queued in the outer stream until the current one ends.
+pub fn concat_all<SInner: Stream, SOuter: Stream<Item=SInner>>(s: SOuter) -> ConcatAll<SOuter> {
+    MergeMap::new(s, identity as fn(SInner) -> SInner, 1)
 }
 
 impl<S: Stream> Stream for ChainVec<S> {
@@ -49,7 +51,6 @@ impl<S: Stream> Stream for ChainVec<S> {
                     if len == *current {
                         return Poll::Ready(None)
                     }
-                    ()
                 }
                 x => return x
             }
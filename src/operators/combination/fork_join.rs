@@ -1,14 +1,107 @@
-use futures::{Stream, StreamExt, FutureExt, future};
-
-pub fn fork_join<S1: Stream, S2: Stream>(s1: S1, s2: S2) -> 
-    impl Stream<Item=(S1::Item, S2::Item)> 
-{
-    let f = future::join(
-            s1.fold(None, |_, x| future::ready(Some(x))), 
-            s2.fold(None, |_, x| future::ready(Some(x)))
-        ).map(|x| match x {
-            (Some(x1), Some(x2)) => Some((x1, x2)),
-            _ => None
-        }).into_stream().take_while(|x| future::ready(x.is_some())).map(|x| x.unwrap());
-    f
-}
\ No newline at end of file
+use futures::task::Poll;
+use futures::task::Context;
+use std::pin::Pin;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use pin_project::pin_project;
+
+/// Generate ForkJoinN for N heterogeneous streams yielding a flat tuple of
+/// each stream's last value. If any stream ends without value, the result
+/// ends right away without value.
+macro_rules! fork_join_n {
+    ($name:ident, $proj:ident, $fn_name:ident, $(($S:ident, $s:ident, $last:ident)),+) => {
+        #[pin_project(project = $proj)]
+        #[derive(Debug)]
+        #[must_use = "streams do nothing unless polled"]
+        pub struct $name<$($S: Stream),+> {
+            $(
+                #[pin]
+                $s: Fuse<$S>,
+            )+
+            $(
+                $last: Option<$S::Item>,
+            )+
+            done: bool,
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fn_name<$($S: Stream),+>($($s: $S),+) -> $name<$($S),+> {
+            $name::new($($s),+)
+        }
+
+        impl<$($S: Stream),+> $name<$($S),+> {
+            #[allow(clippy::too_many_arguments)]
+            pub fn new($($s: $S),+) -> $name<$($S),+> {
+                $name {
+                    $($s: $s.fuse(),)+
+                    $($last: None,)+
+                    done: false,
+                }
+            }
+        }
+
+        impl<$($S: Stream),+> Stream for $name<$($S),+> {
+            type Item = ($($S::Item),+);
+
+            fn poll_next(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                let $proj { $(mut $s,)+ $($last,)+ done } = self.project();
+                if *done {
+                    return Poll::Ready(None)
+                }
+                $(
+                    while let Poll::Ready(Some(item)) = $s.as_mut().poll_next(cx) {
+                        *$last = Some(item);
+                    }
+                    if $s.is_done() && $last.is_none() {
+                        *done = true;
+                        return Poll::Ready(None)
+                    }
+                )+
+                if true $(&& $s.is_done())+ {
+                    *done = true;
+                    Poll::Ready(Some(($($last.take().unwrap()),+)))
+                } else {
+                    Poll::Pending
+                }
+            }
+        }
+    };
+}
+
+fork_join_n!(ForkJoin, ForkJoinProj, fork_join,
+    (S1, s1, last1), (S2, s2, last2));
+fork_join_n!(ForkJoin3, ForkJoin3Proj, fork_join3,
+    (S1, s1, last1), (S2, s2, last2), (S3, s3, last3));
+fork_join_n!(ForkJoin4, ForkJoin4Proj, fork_join4,
+    (S1, s1, last1), (S2, s2, last2), (S3, s3, last3), (S4, s4, last4));
+fork_join_n!(ForkJoin5, ForkJoin5Proj, fork_join5,
+    (S1, s1, last1), (S2, s2, last2), (S3, s3, last3), (S4, s4, last4),
+    (S5, s5, last5));
+fork_join_n!(ForkJoin6, ForkJoin6Proj, fork_join6,
+    (S1, s1, last1), (S2, s2, last2), (S3, s3, last3), (S4, s4, last4),
+    (S5, s5, last5), (S6, s6, last6));
+fork_join_n!(ForkJoin7, ForkJoin7Proj, fork_join7,
+    (S1, s1, last1), (S2, s2, last2), (S3, s3, last3), (S4, s4, last4),
+    (S5, s5, last5), (S6, s6, last6), (S7, s7, last7));
+fork_join_n!(ForkJoin8, ForkJoin8Proj, fork_join8,
+    (S1, s1, last1), (S2, s2, last2), (S3, s3, last3), (S4, s4, last4),
+    (S5, s5, last5), (S6, s6, last6), (S7, s7, last7), (S8, s8, last8));
+fork_join_n!(ForkJoin9, ForkJoin9Proj, fork_join9,
+    (S1, s1, last1), (S2, s2, last2), (S3, s3, last3), (S4, s4, last4),
+    (S5, s5, last5), (S6, s6, last6), (S7, s7, last7), (S8, s8, last8),
+    (S9, s9, last9));
+fork_join_n!(ForkJoin10, ForkJoin10Proj, fork_join10,
+    (S1, s1, last1), (S2, s2, last2), (S3, s3, last3), (S4, s4, last4),
+    (S5, s5, last5), (S6, s6, last6), (S7, s7, last7), (S8, s8, last8),
+    (S9, s9, last9), (S10, s10, last10));
+fork_join_n!(ForkJoin11, ForkJoin11Proj, fork_join11,
+    (S1, s1, last1), (S2, s2, last2), (S3, s3, last3), (S4, s4, last4),
+    (S5, s5, last5), (S6, s6, last6), (S7, s7, last7), (S8, s8, last8),
+    (S9, s9, last9), (S10, s10, last10), (S11, s11, last11));
+fork_join_n!(ForkJoin12, ForkJoin12Proj, fork_join12,
+    (S1, s1, last1), (S2, s2, last2), (S3, s3, last3), (S4, s4, last4),
+    (S5, s5, last5), (S6, s6, last6), (S7, s7, last7), (S8, s8, last8),
+    (S9, s9, last9), (S10, s10, last10), (S11, s11, last11), (S12, s12, last12));
@@ -0,0 +1,131 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::pin::Pin;
+use futures::{Stream, StreamExt};
+use futures::stream::{Fuse, Iter};
+use pin_project::pin_project;
+use std::convert::identity;
+use super::super::transform::merge_map::MergeMap;
+
+pub type MergeAll<S> = MergeMap<S, fn(<S as Stream>::Item) -> <S as Stream>::Item, <S as Stream>::Item>;
+pub type MergeVec<S> = MergeAll<Iter<std::vec::IntoIter<S>>>;
+
+/// Merge the inner streams as they come from the outer stream.
+pub fn merge_all<SInner: Stream, SOuter: Stream<Item=SInner>>(s: SOuter) -> MergeAll<SOuter> {
+    merge_all_with_concurrency(s, usize::MAX)
+}
+
+/// merge_all running at most concurrent inner streams at a time. The next
+/// inner stream is taken from the outer stream once a running one ends.
+pub fn merge_all_with_concurrency<SInner: Stream, SOuter: Stream<Item=SInner>>(s: SOuter, concurrent: usize) -> MergeAll<SOuter> {
+    MergeMap::new(s, identity as fn(SInner) -> SInner, concurrent)
+}
+
+/// Merge any number of streams of the same type. The ready streams are
+/// polled in turn, so a busy stream can not starve the others.
+pub fn merge_vec<S: Stream>(s_vec: Vec<S>) -> MergeVec<S> {
+    merge_vec_with_concurrency(s_vec, usize::MAX)
+}
+
+/// merge_vec running at most concurrent streams at a time, in the order of
+/// the vector. The next stream starts once a running one ends.
+pub fn merge_vec_with_concurrency<S: Stream>(s_vec: Vec<S>, concurrent: usize) -> MergeVec<S> {
+    merge_all_with_concurrency(futures::stream::iter(s_vec), concurrent)
+}
+
+/// Generate MergeN for N streams of different types with the same item type.
+/// The streams are polled in turn, starting from a different one each time,
+/// so a busy stream can not starve the others. It ends when all streams end.
+macro_rules! merge_n {
+    ($name:ident, $proj:ident, $fn_name:ident, $n:expr,
+        ($idx1:tt, $S1:ident, $s1:ident), $(($idx:tt, $S:ident, $s:ident)),+) => {
+        #[pin_project(project = $proj)]
+        #[derive(Debug)]
+        #[must_use = "streams do nothing unless polled"]
+        pub struct $name<$S1: Stream, $($S: Stream<Item = $S1::Item>),+> {
+            #[pin]
+            $s1: Fuse<$S1>,
+            $(
+                #[pin]
+                $s: Fuse<$S>,
+            )+
+            next: usize,
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fn_name<$S1: Stream, $($S: Stream<Item = $S1::Item>),+>(
+            $s1: $S1, $($s: $S),+
+        ) -> $name<$S1, $($S),+> {
+            $name::new($s1, $($s),+)
+        }
+
+        impl<$S1: Stream, $($S: Stream<Item = $S1::Item>),+> $name<$S1, $($S),+> {
+            #[allow(clippy::too_many_arguments)]
+            pub fn new($s1: $S1, $($s: $S),+) -> $name<$S1, $($S),+> {
+                $name {
+                    $s1: $s1.fuse(),
+                    $($s: $s.fuse(),)+
+                    next: 0,
+                }
+            }
+        }
+
+        impl<$S1: Stream, $($S: Stream<Item = $S1::Item>),+> Stream for $name<$S1, $($S),+> {
+            type Item = $S1::Item;
+
+            fn poll_next(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                let $proj { mut $s1, $(mut $s,)+ next } = self.project();
+                let start = *next;
+                *next = (start + 1) % $n;
+                let mut all_done = true;
+                for i in 0..$n {
+                    let r = match (start + i) % $n {
+                        $idx1 => $s1.as_mut().poll_next(cx),
+                        $($idx => $s.as_mut().poll_next(cx),)+
+                        _ => unreachable!(),
+                    };
+                    match r {
+                        Poll::Ready(Some(item)) => return Poll::Ready(Some(item)),
+                        Poll::Ready(None) => (),
+                        Poll::Pending => all_done = false,
+                    }
+                }
+                if all_done {
+                    Poll::Ready(None)
+                } else {
+                    Poll::Pending
+                }
+            }
+        }
+    };
+}
+
+merge_n!(Merge3, Merge3Proj, merge3, 3,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3));
+merge_n!(Merge4, Merge4Proj, merge4, 4,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4));
+merge_n!(Merge5, Merge5Proj, merge5, 5,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5));
+merge_n!(Merge6, Merge6Proj, merge6, 6,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6));
+merge_n!(Merge7, Merge7Proj, merge7, 7,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6),
+    (6, S7, s7));
+merge_n!(Merge8, Merge8Proj, merge8, 8,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6),
+    (6, S7, s7), (7, S8, s8));
+merge_n!(Merge9, Merge9Proj, merge9, 9,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6),
+    (6, S7, s7), (7, S8, s8), (8, S9, s9));
+merge_n!(Merge10, Merge10Proj, merge10, 10,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6),
+    (6, S7, s7), (7, S8, s8), (8, S9, s9), (9, S10, s10));
+merge_n!(Merge11, Merge11Proj, merge11, 11,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6),
+    (6, S7, s7), (7, S8, s8), (8, S9, s9), (9, S10, s10), (10, S11, s11));
+merge_n!(Merge12, Merge12Proj, merge12, 12,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6),
+    (6, S7, s7), (7, S8, s8), (8, S9, s9), (9, S10, s10), (10, S11, s11), (11, S12, s12));
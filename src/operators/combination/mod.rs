@@ -2,4 +2,7 @@ pub mod combine_latest;
 pub mod fork_join;
 pub mod concat;
 pub mod race;
-pub mod with_latest_from;
\ No newline at end of file
+pub mod with_latest_from;
+pub mod merge;
+pub mod zip;
+
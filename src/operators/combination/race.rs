@@ -1,65 +1,218 @@
 use futures::task::Poll;
 use futures::task::Context;
 use std::pin::Pin;
-use futures::Stream;
+use std::fmt;
+use futures::{Stream, StreamExt};
+use futures::stream::{Fuse, Iter};
 use pin_project::pin_project;
 
-#[pin_project(project=RaceProj)]
-#[derive(Debug)]
+/// Generate Race and RaceN for N streams of different types with the same
+/// item type. The first stream to respond, either with a value or its end,
+/// is picked and the others are dropped right away. The first poll starts
+/// from the first stream, as rxjs subscribes in order, and each later poll
+/// from the next one, so a tie is not always won by the same one.
+macro_rules! race_n {
+    ($name:ident, $proj:ident, $fn_name:ident,
+        ($idx1:tt, $S1:ident, $s1:ident),
+        $(($idx:tt, $S:ident $(= $D:ident)?, $s:ident)),+) => {
+        #[pin_project(project = $proj)]
+        #[derive(Debug)]
+        #[must_use = "streams do nothing unless polled"]
+        pub struct $name<$S1: Stream, $($S: Stream<Item = $S1::Item> $(= $D)?),+> {
+            #[pin]
+            $s1: Option<$S1>,
+            $(
+                #[pin]
+                $s: Option<$S>,
+            )+
+            picked: Option<usize>,
+            next: usize,
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fn_name<$S1: Stream, $($S: Stream<Item = $S1::Item>),+>(
+            $s1: $S1, $($s: $S),+
+        ) -> $name<$S1, $($S),+> {
+            $name::new($s1, $($s),+)
+        }
+
+        impl<$S1: Stream, $($S: Stream<Item = $S1::Item>),+> $name<$S1, $($S),+> {
+            #[allow(clippy::too_many_arguments)]
+            pub fn new($s1: $S1, $($s: $S),+) -> $name<$S1, $($S),+> {
+                $name { $s1: Some($s1), $($s: Some($s),)+ picked: None, next: 0 }
+            }
+        }
+
+        impl<$S1: Stream, $($S: Stream<Item = $S1::Item>),+> Stream for $name<$S1, $($S),+> {
+            type Item = $S1::Item;
+
+            fn poll_next(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                let $proj { mut $s1, $(mut $s,)+ picked, next } = self.project();
+                let n = [$idx1, $($idx),+].len();
+                let (start, count) = match *picked {
+                    Some(i) => (i, 1),
+                    None => (*next, n),
+                };
+                for k in 0..count {
+                    let i = start.wrapping_add(k) % n;
+                    // Only the picked stream is left once picked
+                    let polled = match i {
+                        $idx1 => $s1.as_mut().as_pin_mut().unwrap().poll_next(cx),
+                        $($idx => $s.as_mut().as_pin_mut().unwrap().poll_next(cx),)+
+                        _ => unreachable!(),
+                    };
+                    match polled {
+                        Poll::Pending => (),
+                        x => {
+                            if picked.is_none() {
+                                *picked = Some(i);
+                                if i != $idx1 {
+                                    $s1.set(None);
+                                }
+                                $(
+                                    if i != $idx {
+                                        $s.set(None);
+                                    }
+                                )+
+                            }
+                            return x
+                        }
+                    }
+                }
+                *next = next.wrapping_add(1);
+                Poll::Pending
+            }
+        }
+    };
+}
+
+race_n!(Race, RaceProj, race,
+    (0, S1, s1), (1, S2 = S1, s2));
+
+race_n!(Race3, Race3Proj, race3,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3));
+race_n!(Race4, Race4Proj, race4,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4));
+race_n!(Race5, Race5Proj, race5,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5));
+race_n!(Race6, Race6Proj, race6,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6));
+race_n!(Race7, Race7Proj, race7,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6),
+    (6, S7, s7));
+race_n!(Race8, Race8Proj, race8,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6),
+    (6, S7, s7), (7, S8, s8));
+race_n!(Race9, Race9Proj, race9,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6),
+    (6, S7, s7), (7, S8, s8), (8, S9, s9));
+race_n!(Race10, Race10Proj, race10,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6),
+    (6, S7, s7), (7, S8, s8), (8, S9, s9), (9, S10, s10));
+race_n!(Race11, Race11Proj, race11,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6),
+    (6, S7, s7), (7, S8, s8), (8, S9, s9), (9, S10, s10), (10, S11, s11));
+race_n!(Race12, Race12Proj, race12,
+    (0, S1, s1), (1, S2, s2), (2, S3, s3), (3, S4, s4), (4, S5, s5), (5, S6, s6),
+    (6, S7, s7), (7, S8, s8), (8, S9, s9), (9, S10, s10), (10, S11, s11), (11, S12, s12));
+
+#[pin_project(project = RaceAllProj)]
 #[must_use = "streams do nothing unless polled"]
-pub struct Race<S: Stream> {
+pub struct RaceAll<S>
+    where S: Stream, S::Item: Stream
+{
+    // Dropped once a stream is picked, so no more streams join the race
     #[pin]
-    s1: S,
-    #[pin]
-    s2: S,
-    state: RaceState,
+    s: Option<Fuse<S>>,
+    // Boxed, so adding inner streams doesn't move the pinned ones
+    inners: Vec<Pin<Box<Fuse<S::Item>>>>,
+    picked: Option<Pin<Box<Fuse<S::Item>>>>,
+    next: usize,
 }
 
-#[derive(Debug)]
-enum RaceState {
-    Pending,
-    Pick1,
-    Pick2,
+// Derived Debug would not require the inner streams to be Debug
+impl<S> fmt::Debug for RaceAll<S>
+    where S: Stream + fmt::Debug, S::Item: Stream + fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RaceAll")
+            .field("s", &self.s)
+            .field("inners", &self.inners)
+            .field("picked", &self.picked)
+            .field("next", &self.next)
+            .finish()
+    }
 }
 
-impl<S: Stream> Race<S> {
-    pub fn new(stream1: S, stream2: S) -> Race<S>
-    {
-        Race{s1: stream1, s2: stream2, state: RaceState::Pending}
+pub type RaceVec<S> = RaceAll<Iter<std::vec::IntoIter<S>>>;
+
+/// Race the inner streams of the outer stream. Unlike rxjs, the inner
+/// streams join the race as they come rather than once the outer stream
+/// ends, and the outer stream is dropped along with the losers.
+pub fn race_all<S>(s: S) -> RaceAll<S>
+    where S: Stream, S::Item: Stream
+{
+    RaceAll {
+        s: Some(s.fuse()),
+        inners: Vec::new(),
+        picked: None,
+        next: 0,
     }
 }
 
-pub fn race<S: Stream>(s1: S, s2: S) -> Race<S> {
-    Race::new(s1, s2)
+/// Race any number of streams of the same type. It ends right away when
+/// there is no stream.
+pub fn race_vec<S: Stream>(s_vec: Vec<S>) -> RaceVec<S> {
+    race_all(futures::stream::iter(s_vec))
 }
 
-impl<S: Stream> Stream for Race<S> {
-    type Item = S::Item;
+impl<S> Stream for RaceAll<S>
+    where S: Stream, S::Item: Stream
+{
+    type Item = <S::Item as Stream>::Item;
 
     fn poll_next(
-        self: Pin<&mut Self>, 
+        self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
-        let this = self.project();
-        match this.state {
-            RaceState::Pending => {
-                match this.s1.poll_next(cx) {
-                    Poll::Pending => (),
-                    x => {
-                        *this.state = RaceState::Pick1;
-                        return x
-                    }
-                };
-                match this.s2.poll_next(cx) {
-                    Poll::Pending => Poll::Pending,
-                    x => {
-                        *this.state = RaceState::Pick2;
-                        return x
-                    }
+        let RaceAllProj { mut s, inners, picked, next } = self.project();
+        if let Some(picked) = picked {
+            return picked.as_mut().poll_next(cx)
+        }
+        let mut outer_done = true;
+        let mut outer_ready = false;
+        if let Some(mut outer) = s.as_mut().as_pin_mut() {
+            // One inner stream per call, the outer stream may never end
+            if let Poll::Ready(Some(inner)) = outer.as_mut().poll_next(cx) {
+                inners.push(Box::pin(inner.fuse()));
+                outer_ready = true;
+            }
+            outer_done = outer.is_done();
+        }
+        let n = inners.len();
+        for k in 0..n {
+            let i = next.wrapping_add(k) % n;
+            match inners[i].as_mut().poll_next(cx) {
+                Poll::Pending => (),
+                x => {
+                    *picked = Some(inners.swap_remove(i));
+                    inners.clear();
+                    s.set(None);
+                    return x
                 }
-            },
-            RaceState::Pick1 => return this.s1.poll_next(cx),
-            RaceState::Pick2 => return this.s2.poll_next(cx),
+            }
         }
+        *next = next.wrapping_add(1);
+        if outer_done && n == 0 {
+            return Poll::Ready(None)
+        }
+        if outer_ready {
+            // The outer stream hasn't registered the waker, poll it again
+            cx.waker().wake_by_ref();
+        }
+        Poll::Pending
     }
-}
\ No newline at end of file
+}
@@ -64,25 +64,143 @@ impl<S1, S2> Stream for WithLatestFrom<S1, S2>
             Poll::Ready(None) | Poll::Pending => false
         };
 
-        match other.as_mut().poll_next(cx) {
+        // Poll the other stream once, draining it here would never end
+        // when it is always ready
+        let other_ready = match other.as_mut().poll_next(cx) {
             Poll::Ready(Some(item2)) => {
                 *queued_other = Some(item2);
+                true
             }
-            Poll::Ready(None) | Poll::Pending => ()
+            Poll::Ready(None) | Poll::Pending => false
         };
         // End the stream if source is done, any time
         // Or if other is done before emit anything
         if source.is_done() || other.is_done() && queued_other.is_none() {
             Poll::Ready(None)
         } 
-        else if source_ready {
-            if queued_other.is_some() {
-                Poll::Ready(Some((queued_source.take().unwrap(), queued_other.clone().unwrap())))
-            } else {
-                Poll::Pending
-            }
+        else if source_ready && queued_other.is_some() {
+            Poll::Ready(Some((queued_source.take().unwrap(), queued_other.clone().unwrap())))
         } else {
+            if source_ready || other_ready {
+                // The ready stream hasn't registered the waker, poll it again
+                cx.waker().wake_by_ref();
+            }
             Poll::Pending
         }
     }
-}
\ No newline at end of file
+}
+
+/// Generate WithLatestFromN for a source and N other streams of any type,
+/// yielding a flat tuple of the source value and the latest of each other.
+/// It follows the same rules as the one other stream version above.
+macro_rules! with_latest_from_n {
+    ($name:ident, $proj:ident, $(($O:ident, $other:ident, $queued:ident)),+) => {
+        #[pin_project(project = $proj)]
+        #[derive(Debug)]
+        #[must_use = "streams do nothing unless polled"]
+        pub struct $name<S, $($O),+>
+            where S: Stream, $($O: Stream, $O::Item: Clone),+
+        {
+            #[pin]
+            source: Fuse<S>,
+            $(
+                #[pin]
+                $other: Fuse<$O>,
+            )+
+            queued_source: Option<S::Item>,
+            $(
+                $queued: Option<$O::Item>,
+            )+
+        }
+
+        impl<S, $($O),+> $name<S, $($O),+>
+            where S: Stream, $($O: Stream, $O::Item: Clone),+
+        {
+            #[allow(clippy::too_many_arguments)]
+            pub fn new(source: S, $($other: $O),+) -> $name<S, $($O),+> {
+                $name {
+                    source: source.fuse(),
+                    $($other: $other.fuse(),)+
+                    queued_source: None,
+                    $($queued: None,)+
+                }
+            }
+        }
+
+        impl<S, $($O),+> Stream for $name<S, $($O),+>
+            where S: Stream, $($O: Stream, $O::Item: Clone),+
+        {
+            type Item = (S::Item, $($O::Item),+);
+
+            fn poll_next(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                let $proj { mut source, $(mut $other,)+ queued_source, $($queued,)+ } = self.project();
+                let source_ready = match source.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(item)) => {
+                        *queued_source = Some(item);
+                        true
+                    }
+                    Poll::Ready(None) | Poll::Pending => false
+                };
+                let mut other_ready = false;
+                $(
+                    if let Poll::Ready(Some(item)) = $other.as_mut().poll_next(cx) {
+                        *$queued = Some(item);
+                        other_ready = true;
+                    }
+                )+
+                // End the stream if source is done, any time
+                // Or if any other is done before emit anything
+                if source.is_done() $(|| $other.is_done() && $queued.is_none())+ {
+                    Poll::Ready(None)
+                } else if source_ready $(&& $queued.is_some())+ {
+                    Poll::Ready(Some((queued_source.take().unwrap(), $($queued.clone().unwrap()),+)))
+                } else {
+                    if source_ready || other_ready {
+                        // The ready streams haven't registered the waker, poll them again
+                        cx.waker().wake_by_ref();
+                    }
+                    Poll::Pending
+                }
+            }
+        }
+    };
+}
+
+with_latest_from_n!(WithLatestFrom2, WithLatestFrom2Proj,
+    (O1, other1, queued_other1), (O2, other2, queued_other2));
+with_latest_from_n!(WithLatestFrom3, WithLatestFrom3Proj,
+    (O1, other1, queued_other1), (O2, other2, queued_other2), (O3, other3, queued_other3));
+with_latest_from_n!(WithLatestFrom4, WithLatestFrom4Proj,
+    (O1, other1, queued_other1), (O2, other2, queued_other2), (O3, other3, queued_other3),
+    (O4, other4, queued_other4));
+with_latest_from_n!(WithLatestFrom5, WithLatestFrom5Proj,
+    (O1, other1, queued_other1), (O2, other2, queued_other2), (O3, other3, queued_other3),
+    (O4, other4, queued_other4), (O5, other5, queued_other5));
+with_latest_from_n!(WithLatestFrom6, WithLatestFrom6Proj,
+    (O1, other1, queued_other1), (O2, other2, queued_other2), (O3, other3, queued_other3),
+    (O4, other4, queued_other4), (O5, other5, queued_other5), (O6, other6, queued_other6));
+with_latest_from_n!(WithLatestFrom7, WithLatestFrom7Proj,
+    (O1, other1, queued_other1), (O2, other2, queued_other2), (O3, other3, queued_other3),
+    (O4, other4, queued_other4), (O5, other5, queued_other5), (O6, other6, queued_other6),
+    (O7, other7, queued_other7));
+with_latest_from_n!(WithLatestFrom8, WithLatestFrom8Proj,
+    (O1, other1, queued_other1), (O2, other2, queued_other2), (O3, other3, queued_other3),
+    (O4, other4, queued_other4), (O5, other5, queued_other5), (O6, other6, queued_other6),
+    (O7, other7, queued_other7), (O8, other8, queued_other8));
+with_latest_from_n!(WithLatestFrom9, WithLatestFrom9Proj,
+    (O1, other1, queued_other1), (O2, other2, queued_other2), (O3, other3, queued_other3),
+    (O4, other4, queued_other4), (O5, other5, queued_other5), (O6, other6, queued_other6),
+    (O7, other7, queued_other7), (O8, other8, queued_other8), (O9, other9, queued_other9));
+with_latest_from_n!(WithLatestFrom10, WithLatestFrom10Proj,
+    (O1, other1, queued_other1), (O2, other2, queued_other2), (O3, other3, queued_other3),
+    (O4, other4, queued_other4), (O5, other5, queued_other5), (O6, other6, queued_other6),
+    (O7, other7, queued_other7), (O8, other8, queued_other8), (O9, other9, queued_other9),
+    (O10, other10, queued_other10));
+with_latest_from_n!(WithLatestFrom11, WithLatestFrom11Proj,
+    (O1, other1, queued_other1), (O2, other2, queued_other2), (O3, other3, queued_other3),
+    (O4, other4, queued_other4), (O5, other5, queued_other5), (O6, other6, queued_other6),
+    (O7, other7, queued_other7), (O8, other8, queued_other8), (O9, other9, queued_other9),
+    (O10, other10, queued_other10), (O11, other11, queued_other11));
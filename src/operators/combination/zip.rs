@@ -0,0 +1,270 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::pin::Pin;
+use std::marker::PhantomData;
+use std::fmt;
+use futures::{Stream, StreamExt};
+use futures::stream::{Fuse, Iter};
+use pin_project::pin_project;
+
+/// When a zip of many streams completes, and what it yields.
+pub trait ZipPolicy<T> {
+    type Item;
+    /// Complete as soon as any stream ends, rather than when all end.
+    fn ends_on_first() -> bool;
+    /// The value of a stream in a yielded vector, None if it ended.
+    fn item(v: Option<T>) -> Self::Item;
+}
+
+/// Complete when the first stream ends, and yield Vec<T>, as in rxjs.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct EndOnFirst;
+
+impl<T> ZipPolicy<T> for EndOnFirst {
+    type Item = T;
+
+    fn ends_on_first() -> bool {
+        true
+    }
+
+    fn item(v: Option<T>) -> T {
+        v.unwrap()
+    }
+}
+
+/// Complete when all streams end, and yield Vec<Option<T>> with None for
+/// the streams which ended already.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct PadWithNone;
+
+impl<T> ZipPolicy<T> for PadWithNone {
+    type Item = Option<T>;
+
+    fn ends_on_first() -> bool {
+        false
+    }
+
+    fn item(v: Option<T>) -> Option<T> {
+        v
+    }
+}
+
+#[pin_project(project = ZipAllProj)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ZipAll<S, P = EndOnFirst>
+    where S: Stream, S::Item: Stream
+{
+    #[pin]
+    s: Fuse<S>,
+    // Boxed, so adding inner streams doesn't move the pinned ones
+    inners: Vec<Pin<Box<Fuse<S::Item>>>>,
+    slots: Vec<Option<<S::Item as Stream>::Item>>,
+    done: bool,
+    policy: PhantomData<P>,
+}
+
+// Derived Debug would not require the values of the inner streams to be Debug
+impl<S, P> fmt::Debug for ZipAll<S, P>
+    where S: Stream + fmt::Debug, S::Item: Stream + fmt::Debug,
+          <S::Item as Stream>::Item: fmt::Debug
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ZipAll")
+            .field("s", &self.s)
+            .field("inners", &self.inners)
+            .field("slots", &self.slots)
+            .field("done", &self.done)
+            .finish()
+    }
+}
+
+pub type ZipVec<S, P = EndOnFirst> = ZipAll<Iter<std::vec::IntoIter<S>>, P>;
+
+/// Zip the inner streams of the outer stream. As the width of the vectors
+/// is only known then, nothing is yielded until the outer stream ends.
+/// The inner streams are subscribed as they come though.
+pub fn zip_all<S>(s: S) -> ZipAll<S>
+    where S: Stream, S::Item: Stream
+{
+    zip_all_with_policy(s, EndOnFirst)
+}
+
+pub fn zip_all_with_policy<S, P>(s: S, _policy: P) -> ZipAll<S, P>
+    where S: Stream, S::Item: Stream, P: ZipPolicy<<S::Item as Stream>::Item>
+{
+    ZipAll {
+        s: s.fuse(),
+        inners: Vec::new(),
+        slots: Vec::new(),
+        done: false,
+        policy: PhantomData,
+    }
+}
+
+/// Zip any number of streams of the same type, yielding the n-th values of
+/// all streams together.
+pub fn zip_vec<S: Stream>(s_vec: Vec<S>) -> ZipVec<S> {
+    zip_all(futures::stream::iter(s_vec))
+}
+
+pub fn zip_vec_with_policy<S: Stream, P: ZipPolicy<S::Item>>(s_vec: Vec<S>, policy: P) -> ZipVec<S, P> {
+    zip_all_with_policy(futures::stream::iter(s_vec), policy)
+}
+
+impl<S, P> Stream for ZipAll<S, P>
+    where S: Stream, S::Item: Stream, P: ZipPolicy<<S::Item as Stream>::Item>
+{
+    type Item = Vec<P::Item>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let ZipAllProj { mut s, inners, slots, done, .. } = self.project();
+        if *done {
+            return Poll::Ready(None)
+        }
+        // One inner stream per call, the outer stream may never end
+        let outer_ready = match s.as_mut().poll_next(cx) {
+            Poll::Ready(Some(inner)) => {
+                inners.push(Box::pin(inner.fuse()));
+                slots.push(None);
+                true
+            }
+            Poll::Ready(None) | Poll::Pending => false,
+        };
+        // Each stream is polled for one value ahead only, the later values
+        // wait in the stream
+        let mut waiting = false;
+        let mut ended = 0;
+        for (inner, slot) in inners.iter_mut().zip(slots.iter_mut()) {
+            if slot.is_none() {
+                match inner.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(v)) => *slot = Some(v),
+                    Poll::Ready(None) => ended += 1,
+                    Poll::Pending => waiting = true,
+                }
+            }
+        }
+        if !s.is_done() {
+            if outer_ready {
+                // The outer stream hasn't registered the waker, poll it again
+                cx.waker().wake_by_ref();
+            }
+            return Poll::Pending
+        }
+        if ended == inners.len() || ended > 0 && P::ends_on_first() {
+            *done = true;
+            return Poll::Ready(None)
+        }
+        if waiting {
+            return Poll::Pending
+        }
+        Poll::Ready(Some(slots.iter_mut().map(|slot| P::item(slot.take())).collect()))
+    }
+}
+
+/// Generate ZipN for N heterogeneous streams yielding a flat tuple of their
+/// n-th values. Each stream is polled for one value ahead only, and it ends
+/// as soon as a stream ends without a value waiting, as zip does.
+macro_rules! zip_n {
+    ($name:ident, $proj:ident, $fn_name:ident, $(($S:ident, $s:ident, $slot:ident)),+) => {
+        #[pin_project(project = $proj)]
+        #[derive(Debug)]
+        #[must_use = "streams do nothing unless polled"]
+        pub struct $name<$($S: Stream),+> {
+            $(
+                #[pin]
+                $s: Fuse<$S>,
+            )+
+            $(
+                $slot: Option<$S::Item>,
+            )+
+            done: bool,
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        pub fn $fn_name<$($S: Stream),+>($($s: $S),+) -> $name<$($S),+> {
+            $name::new($($s),+)
+        }
+
+        impl<$($S: Stream),+> $name<$($S),+> {
+            #[allow(clippy::too_many_arguments)]
+            pub fn new($($s: $S),+) -> $name<$($S),+> {
+                $name {
+                    $($s: $s.fuse(),)+
+                    $($slot: None,)+
+                    done: false,
+                }
+            }
+        }
+
+        impl<$($S: Stream),+> Stream for $name<$($S),+> {
+            type Item = ($($S::Item),+);
+
+            fn poll_next(
+                self: Pin<&mut Self>,
+                cx: &mut Context<'_>,
+            ) -> Poll<Option<Self::Item>> {
+                let $proj { $(mut $s,)+ $($slot,)+ done } = self.project();
+                if *done {
+                    return Poll::Ready(None)
+                }
+                let mut any_ready = false;
+                $(
+                    if $slot.is_none() {
+                        if let Poll::Ready(Some(item)) = $s.as_mut().poll_next(cx) {
+                            *$slot = Some(item);
+                            any_ready = true;
+                        }
+                    }
+                )+
+                if false $(|| $s.is_done() && $slot.is_none())+ {
+                    *done = true;
+                    Poll::Ready(None)
+                } else if true $(&& $slot.is_some())+ {
+                    Poll::Ready(Some(($($slot.take().unwrap()),+)))
+                } else {
+                    if any_ready {
+                        // The ready streams haven't registered the waker, poll them again
+                        cx.waker().wake_by_ref();
+                    }
+                    Poll::Pending
+                }
+            }
+        }
+    };
+}
+
+zip_n!(Zip3, Zip3Proj, zip3,
+    (S1, s1, slot1), (S2, s2, slot2), (S3, s3, slot3));
+zip_n!(Zip4, Zip4Proj, zip4,
+    (S1, s1, slot1), (S2, s2, slot2), (S3, s3, slot3), (S4, s4, slot4));
+zip_n!(Zip5, Zip5Proj, zip5,
+    (S1, s1, slot1), (S2, s2, slot2), (S3, s3, slot3), (S4, s4, slot4),
+    (S5, s5, slot5));
+zip_n!(Zip6, Zip6Proj, zip6,
+    (S1, s1, slot1), (S2, s2, slot2), (S3, s3, slot3), (S4, s4, slot4),
+    (S5, s5, slot5), (S6, s6, slot6));
+zip_n!(Zip7, Zip7Proj, zip7,
+    (S1, s1, slot1), (S2, s2, slot2), (S3, s3, slot3), (S4, s4, slot4),
+    (S5, s5, slot5), (S6, s6, slot6), (S7, s7, slot7));
+zip_n!(Zip8, Zip8Proj, zip8,
+    (S1, s1, slot1), (S2, s2, slot2), (S3, s3, slot3), (S4, s4, slot4),
+    (S5, s5, slot5), (S6, s6, slot6), (S7, s7, slot7), (S8, s8, slot8));
+zip_n!(Zip9, Zip9Proj, zip9,
+    (S1, s1, slot1), (S2, s2, slot2), (S3, s3, slot3), (S4, s4, slot4),
+    (S5, s5, slot5), (S6, s6, slot6), (S7, s7, slot7), (S8, s8, slot8),
+    (S9, s9, slot9));
+zip_n!(Zip10, Zip10Proj, zip10,
+    (S1, s1, slot1), (S2, s2, slot2), (S3, s3, slot3), (S4, s4, slot4),
+    (S5, s5, slot5), (S6, s6, slot6), (S7, s7, slot7), (S8, s8, slot8),
+    (S9, s9, slot9), (S10, s10, slot10));
+zip_n!(Zip11, Zip11Proj, zip11,
+    (S1, s1, slot1), (S2, s2, slot2), (S3, s3, slot3), (S4, s4, slot4),
+    (S5, s5, slot5), (S6, s6, slot6), (S7, s7, slot7), (S8, s8, slot8),
+    (S9, s9, slot9), (S10, s10, slot10), (S11, s11, slot11));
+zip_n!(Zip12, Zip12Proj, zip12,
+    (S1, s1, slot1), (S2, s2, slot2), (S3, s3, slot3), (S4, s4, slot4),
+    (S5, s5, slot5), (S6, s6, slot6), (S7, s7, slot7), (S8, s8, slot8),
+    (S9, s9, slot9), (S10, s10, slot10), (S11, s11, slot11), (S12, s12, slot12));
@@ -0,0 +1,63 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::pin::Pin;
+use futures::{Stream, TryStream};
+use pin_project::pin_project;
+use super::short_circuit::{ErrorSlot, TakeOks};
+use super::super::combination::concat::{concat_vec, ChainVec};
+
+/// Switch to the replacement stream made from the first error.
+#[pin_project(project=CatchErrorProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct CatchError<S, F, S2> {
+    #[pin]
+    s: S,
+    f: Option<F>,
+    #[pin]
+    replacement: Option<S2>,
+}
+
+impl<S, F, S2> CatchError<S, F, S2>
+    where S: TryStream, F: FnOnce(S::Error) -> S2, S2: TryStream<Ok = S::Ok>
+{
+    pub fn new(s: S, f: F) -> Self {
+        CatchError {
+            s,
+            f: Some(f),
+            replacement: None,
+        }
+    }
+}
+
+impl<S, F, S2> Stream for CatchError<S, F, S2>
+    where S: TryStream, F: FnOnce(S::Error) -> S2, S2: TryStream<Ok = S::Ok>
+{
+    type Item = Result<S::Ok, S2::Error>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if this.replacement.is_none() {
+            match ready!(this.s.try_poll_next(cx)) {
+                Some(Ok(v)) => return Poll::Ready(Some(Ok(v))),
+                Some(Err(e)) => {
+                    let f = this.f.take().unwrap();
+                    this.replacement.set(Some(f(e)));
+                }
+                None => return Poll::Ready(None),
+            }
+        }
+        this.replacement.as_pin_mut().unwrap().try_poll_next(cx)
+    }
+}
+
+pub type OnErrorResumeNext<S> = ChainVec<TakeOks<S>>;
+
+pub fn on_error_resume_next<S: TryStream>(s_vec: Vec<S>) -> OnErrorResumeNext<S> {
+    // Nobody reads the errors, they are just dropped with the slot
+    let slot = ErrorSlot::default();
+    concat_vec(s_vec.into_iter().map(|s| slot.take_oks(s)).collect())
+}
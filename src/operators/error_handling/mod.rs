@@ -0,0 +1,3 @@
+pub mod short_circuit;
+pub mod retry;
+pub mod catch_error;
@@ -0,0 +1,232 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::collections::hash_map::RandomState;
+use std::hash::{BuildHasher, Hasher};
+use std::pin::Pin;
+use std::time::Duration;
+use futures::{Future, Stream, TryStream};
+use futures::future::{ready, Ready};
+use pin_project::pin_project;
+use super::super::super::scheduler::Scheduler;
+
+/// Decide what to do after an error of the source. The returned future
+/// resolves to Ok to re-create the source, or to Err to give up with the error.
+pub trait RetryNotifier<E> {
+    type Future: Future<Output = Result<(), E>>;
+    fn notify(&mut self, error: E) -> Self::Future;
+}
+
+impl<E, Fut, N> RetryNotifier<E> for N
+    where N: FnMut(E) -> Fut, Fut: Future<Output = Result<(), E>>
+{
+    type Future = Fut;
+
+    fn notify(&mut self, error: E) -> Fut {
+        self(error)
+    }
+}
+
+/// Retry right away, up to the given times in total.
+#[derive(Debug, Clone)]
+pub struct RetryCount {
+    remaining: usize,
+}
+
+impl RetryCount {
+    pub fn new(count: usize) -> Self {
+        RetryCount { remaining: count }
+    }
+}
+
+impl<E> RetryNotifier<E> for RetryCount {
+    type Future = Ready<Result<(), E>>;
+
+    fn notify(&mut self, error: E) -> Self::Future {
+        if self.remaining == 0 {
+            return ready(Err(error))
+        }
+        self.remaining -= 1;
+        ready(Ok(()))
+    }
+}
+
+/// Exponential backoff. The n-th retry waits initial * 2^n milliseconds,
+/// capped at max. With jitter, a random part of the delay up to the given
+/// ratio is taken off, so that many clients failing together don't retry
+/// at the same time.
+#[derive(Debug, Clone)]
+pub struct Backoff {
+    max_retries: usize,
+    initial: u64,
+    max: u64,
+    jitter: f64,
+}
+
+impl Backoff {
+    pub fn new(max_retries: usize, initial: u64, max: u64) -> Self {
+        Backoff {
+            max_retries,
+            initial,
+            max,
+            jitter: 0.0,
+        }
+    }
+
+    /// The ratio is clamped to 0..=1, where 1 picks the delay anywhere from 0
+    /// to the full delay.
+    pub fn with_jitter(self, ratio: f64) -> Self {
+        Backoff {
+            jitter: ratio.clamp(0.0, 1.0),
+            ..self
+        }
+    }
+
+    /// The delay of the n-th retry, counted from 0, before jitter.
+    pub fn delay(&self, attempt: u32) -> u64 {
+        self.initial
+            .saturating_mul(2u64.saturating_pow(attempt))
+            .min(self.max)
+    }
+}
+
+#[derive(Debug)]
+pub struct BackoffNotifier<Sch: Scheduler> {
+    backoff: Backoff,
+    attempt: u32,
+    scheduler: Sch,
+    seed: u64,
+}
+
+impl<Sch: Scheduler> BackoffNotifier<Sch> {
+    pub fn new(backoff: Backoff, scheduler: Sch) -> Self {
+        BackoffNotifier {
+            backoff,
+            attempt: 0,
+            scheduler,
+            // Never 0, which would stick the xorshift below at 0
+            seed: RandomState::new().build_hasher().finish() | 1,
+        }
+    }
+
+    fn random(&mut self) -> f64 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 7;
+        self.seed ^= self.seed << 17;
+        (self.seed >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+impl<E, Sch: Scheduler> RetryNotifier<E> for BackoffNotifier<Sch> {
+    type Future = BackoffDelay<Sch::Delay, E>;
+
+    fn notify(&mut self, error: E) -> Self::Future {
+        if self.attempt as usize >= self.backoff.max_retries {
+            return BackoffDelay { delay: None, error: Some(error) }
+        }
+        let mut delay = self.backoff.delay(self.attempt);
+        if self.backoff.jitter > 0.0 {
+            delay -= (delay as f64 * self.backoff.jitter * self.random()) as u64;
+        }
+        self.attempt += 1;
+        let deadline = self.scheduler.now() + Duration::from_millis(delay);
+        BackoffDelay {
+            delay: Some(self.scheduler.delay_until(deadline)),
+            error: None,
+        }
+    }
+}
+
+/// Wait for the backoff delay, or give up with the error.
+#[pin_project]
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct BackoffDelay<D, E> {
+    #[pin]
+    delay: Option<D>,
+    error: Option<E>,
+}
+
+impl<D: Future<Output = ()>, E> Future for BackoffDelay<D, E> {
+    type Output = Result<(), E>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        if let Some(e) = this.error.take() {
+            return Poll::Ready(Err(e))
+        }
+        match this.delay.as_pin_mut() {
+            Some(delay) => delay.poll(cx).map(Ok),
+            None => Poll::Ready(Ok(())),
+        }
+    }
+}
+
+/// Re-create the source from the factory after an error, as the notifier
+/// decides. Values yielded before the error are kept.
+#[pin_project(project=RetryProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Retry<S: TryStream, F, N: RetryNotifier<S::Error>> {
+    #[pin]
+    s: Option<S>,
+    factory: F,
+    notifier: N,
+    #[pin]
+    waiting: Option<N::Future>,
+    done: bool,
+}
+
+impl<S, F, N> Retry<S, F, N>
+    where S: TryStream, F: FnMut() -> S, N: RetryNotifier<S::Error>
+{
+    pub fn new(s: S, factory: F, notifier: N) -> Self {
+        Retry {
+            s: Some(s),
+            factory,
+            notifier,
+            waiting: None,
+            done: false,
+        }
+    }
+}
+
+impl<S, F, N> Stream for Retry<S, F, N>
+    where S: TryStream, F: FnMut() -> S, N: RetryNotifier<S::Error>
+{
+    type Item = Result<S::Ok, S::Error>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if *this.done {
+            return Poll::Ready(None)
+        }
+        loop {
+            if let Some(waiting) = this.waiting.as_mut().as_pin_mut() {
+                let r = ready!(waiting.poll(cx));
+                this.waiting.set(None);
+                match r {
+                    Ok(()) => this.s.set(Some((this.factory)())),
+                    Err(e) => {
+                        *this.done = true;
+                        return Poll::Ready(Some(Err(e)))
+                    }
+                }
+            }
+            let s = this.s.as_mut().as_pin_mut().unwrap();
+            match ready!(s.try_poll_next(cx)) {
+                Some(Ok(v)) => return Poll::Ready(Some(Ok(v))),
+                Some(Err(e)) => {
+                    this.s.set(None);
+                    this.waiting.set(Some(this.notifier.notify(e)));
+                }
+                None => {
+                    *this.done = true;
+                    return Poll::Ready(None)
+                }
+            }
+        }
+    }
+}
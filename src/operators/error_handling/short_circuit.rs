@@ -0,0 +1,154 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use futures::{Stream, TryStream};
+use pin_project::pin_project;
+use super::super::combination::combine_latest::CombineLatest;
+use super::super::combination::fork_join::ForkJoin;
+use super::super::combination::race::Race;
+use super::super::combination::with_latest_from::WithLatestFrom;
+use super::super::transform::pairwise::Pairwise;
+use super::super::transform::simple_count_buffer::SimpleCountBufferedStream;
+use super::super::transform::overlapped_count_buffer::OverlappedCountBufferedStream;
+use super::super::transform::simple_time_buffer::{SimpleExternalTimeBufferredStream, SimpleTimeBufferredStream};
+use super::super::transform::overlapped_time_buffer::OverlappedTimeBufferedStream;
+use super::super::super::scheduler::DefaultScheduler;
+
+/// The first error met by any of the streams sharing the slot.
+#[derive(Debug)]
+pub struct ErrorSlot<E>(Arc<Mutex<Option<E>>>);
+
+impl<E> Clone for ErrorSlot<E> {
+    fn clone(&self) -> Self {
+        ErrorSlot(self.0.clone())
+    }
+}
+
+impl<E> Default for ErrorSlot<E> {
+    fn default() -> Self {
+        ErrorSlot(Arc::new(Mutex::new(None)))
+    }
+}
+
+impl<E> ErrorSlot<E> {
+    fn record(&self, e: E) {
+        self.0.lock().unwrap().get_or_insert(e);
+    }
+
+    fn take(&self) -> Option<E> {
+        self.0.lock().unwrap().take()
+    }
+
+    /// Wrap a fallible stream so it yields its Ok values, and ends at its
+    /// first Err after putting the error into this slot.
+    pub fn take_oks<S: TryStream<Error = E>>(&self, s: S) -> TakeOks<S> {
+        TakeOks {
+            s,
+            slot: self.clone(),
+            done: false,
+        }
+    }
+}
+
+#[pin_project(project=TakeOksProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct TakeOks<S: TryStream> {
+    #[pin]
+    s: S,
+    slot: ErrorSlot<S::Error>,
+    done: bool,
+}
+
+impl<S: TryStream> Stream for TakeOks<S> {
+    type Item = S::Ok;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(None)
+        }
+        match ready!(this.s.try_poll_next(cx)) {
+            Some(Ok(v)) => Poll::Ready(Some(v)),
+            Some(Err(e)) => {
+                this.slot.record(e);
+                *this.done = true;
+                Poll::Ready(None)
+            }
+            None => {
+                *this.done = true;
+                Poll::Ready(None)
+            }
+        }
+    }
+}
+
+/// Runs an infallible operator over TakeOks wrapped streams, and turns it
+/// back to a fallible stream. As soon as any of the wrapped streams meets an
+/// error, the error is yielded and the stream ends, dropping whatever the
+/// operator would have yielded from then on.
+#[pin_project(project=ShortCircuitProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ShortCircuit<S, E> {
+    #[pin]
+    s: S,
+    slot: ErrorSlot<E>,
+    done: bool,
+}
+
+impl<S: Stream, E> ShortCircuit<S, E> {
+    /// Build the operator with a new slot, which is given to wrap the sources.
+    pub fn new<F: FnOnce(&ErrorSlot<E>) -> S>(f: F) -> Self {
+        let slot = ErrorSlot::default();
+        ShortCircuit {
+            s: f(&slot),
+            slot,
+            done: false,
+        }
+    }
+}
+
+impl<S: Stream, E> Stream for ShortCircuit<S, E> {
+    type Item = Result<S::Item, E>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        if *this.done {
+            return Poll::Ready(None)
+        }
+        let r = this.s.poll_next(cx);
+        // The error may come from any source polled just now, and it wins
+        // over the value the operator made at the same time
+        if let Some(e) = this.slot.take() {
+            *this.done = true;
+            return Poll::Ready(Some(Err(e)))
+        }
+        match r {
+            Poll::Ready(Some(v)) => Poll::Ready(Some(Ok(v))),
+            Poll::Ready(None) => {
+                *this.done = true;
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+pub type TryCombineLatest<S1, S2> = ShortCircuit<CombineLatest<TakeOks<S1>, TakeOks<S2>>, <S1 as TryStream>::Error>;
+pub type TryForkJoin<S1, S2> = ShortCircuit<ForkJoin<TakeOks<S1>, TakeOks<S2>>, <S1 as TryStream>::Error>;
+pub type TryWithLatestFrom<S1, S2> = ShortCircuit<WithLatestFrom<TakeOks<S1>, TakeOks<S2>>, <S1 as TryStream>::Error>;
+pub type TryRace<S1, S2 = S1> = ShortCircuit<Race<TakeOks<S1>, TakeOks<S2>>, <S1 as TryStream>::Error>;
+pub type TryPairwise<S> = ShortCircuit<Pairwise<TakeOks<S>>, <S as TryStream>::Error>;
+pub type TrySimpleCountBufferedStream<S> = ShortCircuit<SimpleCountBufferedStream<TakeOks<S>>, <S as TryStream>::Error>;
+pub type TryOverlappedCountBufferedStream<S> = ShortCircuit<OverlappedCountBufferedStream<TakeOks<S>>, <S as TryStream>::Error>;
+pub type TrySimpleTimeBufferredStream<S, Sch = DefaultScheduler> = ShortCircuit<SimpleTimeBufferredStream<TakeOks<S>, Sch>, <S as TryStream>::Error>;
+pub type TrySimpleExternalTimeBufferredStream<'a, S, Sch = DefaultScheduler> = ShortCircuit<SimpleExternalTimeBufferredStream<'a, TakeOks<S>, Sch>, <S as TryStream>::Error>;
+pub type TryOverlappedTimeBufferedStream<S, Sch = DefaultScheduler> = ShortCircuit<OverlappedTimeBufferedStream<TakeOks<S>, Sch>, <S as TryStream>::Error>;
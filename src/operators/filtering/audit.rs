@@ -0,0 +1,67 @@
+use futures::task::Context;
+use futures::task::Poll;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use std::pin::Pin;
+use pin_project::pin_project;
+use super::duration::{DurationSelector, TimeSpan};
+use super::super::super::scheduler::DefaultScheduler;
+
+/// A value coming while no window is open opens one, and the last value
+/// seen when it closes is yielded. When the stream ends in a window, the
+/// window still runs to yield its value, as in rxjs.
+#[pin_project(project = AuditProj)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Audit<S: Stream, F: DurationSelector<S::Item>> {
+    #[pin]
+    s: Fuse<S>,
+    #[pin]
+    window: Option<F::Duration>,
+    last: Option<S::Item>,
+    selector: F,
+}
+
+pub type AuditTime<S, Sch = DefaultScheduler> = Audit<S, TimeSpan<Sch>>;
+
+impl<S: Stream, F: DurationSelector<S::Item>> Audit<S, F> {
+    pub fn new(s: S, selector: F) -> Self {
+        Audit {
+            s: s.fuse(),
+            window: None,
+            last: None,
+            selector,
+        }
+    }
+}
+
+impl<S: Stream, F: DurationSelector<S::Item>> Stream for Audit<S, F> {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let AuditProj { mut s, mut window, last, selector } = self.project();
+        // One value per call, the window is polled on the next one
+        if let Poll::Ready(Some(v)) = s.as_mut().poll_next(cx) {
+            if window.is_none() {
+                window.set(Some(selector.select(&v)));
+            }
+            *last = Some(v);
+            cx.waker().wake_by_ref();
+            return Poll::Pending
+        }
+        if let Some(w) = window.as_mut().as_pin_mut() {
+            if w.poll_next(cx).is_ready() {
+                window.set(None);
+                return Poll::Ready(last.take())
+            }
+            return Poll::Pending
+        }
+        if s.is_done() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
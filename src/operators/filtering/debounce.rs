@@ -0,0 +1,64 @@
+use futures::task::Context;
+use futures::task::Poll;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use std::pin::Pin;
+use pin_project::pin_project;
+use super::duration::{DurationSelector, TimeSpan};
+use super::super::super::scheduler::DefaultScheduler;
+
+/// Yield a value only once its window closed without another value coming,
+/// each value opening a new window. When the stream ends, the value waiting
+/// is yielded right away.
+#[pin_project(project = DebounceProj)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Debounce<S: Stream, F: DurationSelector<S::Item>> {
+    #[pin]
+    s: Fuse<S>,
+    #[pin]
+    window: Option<F::Duration>,
+    pending: Option<S::Item>,
+    selector: F,
+}
+
+pub type DebounceTime<S, Sch = DefaultScheduler> = Debounce<S, TimeSpan<Sch>>;
+
+impl<S: Stream, F: DurationSelector<S::Item>> Debounce<S, F> {
+    pub fn new(s: S, selector: F) -> Self {
+        Debounce {
+            s: s.fuse(),
+            window: None,
+            pending: None,
+            selector,
+        }
+    }
+}
+
+impl<S: Stream, F: DurationSelector<S::Item>> Stream for Debounce<S, F> {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let DebounceProj { mut s, mut window, pending, selector } = self.project();
+        // One value per call, the new window is polled on the next one
+        if let Poll::Ready(Some(v)) = s.as_mut().poll_next(cx) {
+            window.set(Some(selector.select(&v)));
+            *pending = Some(v);
+            cx.waker().wake_by_ref();
+            return Poll::Pending
+        }
+        if s.is_done() {
+            window.set(None);
+            return Poll::Ready(pending.take())
+        }
+        if let Some(w) = window.as_mut().as_pin_mut() {
+            if w.poll_next(cx).is_ready() {
+                window.set(None);
+                return Poll::Ready(pending.take())
+            }
+        }
+        Poll::Pending
+    }
+}
@@ -0,0 +1,154 @@
+use futures::task::Context;
+use futures::task::Poll;
+use futures::{Stream, StreamExt};
+use futures::stream::{Fuse, Pending};
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::pin::Pin;
+use pin_project::pin_project;
+
+/// Yield the values whose key was not seen before. Whenever flush yields,
+/// the seen keys are forgotten.
+#[pin_project(project = DistinctProj)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Distinct<S, F, K, N> {
+    #[pin]
+    s: Fuse<S>,
+    #[pin]
+    flush: Fuse<N>,
+    key_fn: F,
+    seen: HashSet<K>,
+}
+
+/// Distinct of the values themselves, never flushed.
+pub type DistinctByValue<S> = Distinct<S, fn(&<S as Stream>::Item) -> <S as Stream>::Item, <S as Stream>::Item, Pending<()>>;
+
+impl<S, F, K, N> Distinct<S, F, K, N>
+    where S: Stream, F: FnMut(&S::Item) -> K, K: Eq + Hash, N: Stream
+{
+    pub fn new(s: S, key_fn: F, flush: N) -> Self {
+        Distinct {
+            s: s.fuse(),
+            flush: flush.fuse(),
+            key_fn,
+            seen: HashSet::new(),
+        }
+    }
+}
+
+impl<S, F, K, N> Stream for Distinct<S, F, K, N>
+    where S: Stream, F: FnMut(&S::Item) -> K, K: Eq + Hash, N: Stream
+{
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let DistinctProj { mut s, mut flush, key_fn, seen } = self.project();
+        // Once per call, draining it would never end when it is always ready
+        let flushed = match flush.as_mut().poll_next(cx) {
+            Poll::Ready(Some(_)) => {
+                seen.clear();
+                true
+            }
+            Poll::Ready(None) | Poll::Pending => false,
+        };
+        loop {
+            match s.as_mut().poll_next(cx) {
+                Poll::Ready(Some(v)) => if seen.insert(key_fn(&v)) {
+                    return Poll::Ready(Some(v))
+                },
+                Poll::Ready(None) => return Poll::Ready(None),
+                Poll::Pending => {
+                    if flushed {
+                        // The flush stream hasn't registered the waker, poll it again
+                        cx.waker().wake_by_ref();
+                    }
+                    return Poll::Pending
+                }
+            }
+        }
+    }
+}
+
+/// Yield a value unless compare tells it equals the last yielded one.
+#[pin_project(project = DistinctUntilChangedProj)]
+#[must_use = "streams do nothing unless polled"]
+pub struct DistinctUntilChanged<S: Stream, F> {
+    #[pin]
+    s: S,
+    compare: F,
+    last: Option<S::Item>,
+}
+
+/// DistinctUntilChanged comparing the values with PartialEq.
+pub type DistinctUntilValueChanged<S> = DistinctUntilChanged<S, fn(&<S as Stream>::Item, &<S as Stream>::Item) -> bool>;
+
+impl<S: Stream, F: FnMut(&S::Item, &S::Item) -> bool> DistinctUntilChanged<S, F> where S::Item: Clone {
+    pub fn new(s: S, compare: F) -> Self {
+        DistinctUntilChanged {
+            s,
+            compare,
+            last: None,
+        }
+    }
+}
+
+impl<S: Stream, F: FnMut(&S::Item, &S::Item) -> bool> Stream for DistinctUntilChanged<S, F> where S::Item: Clone {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let DistinctUntilChangedProj { mut s, compare, last } = self.project();
+        while let Some(v) = ready!(s.as_mut().poll_next(cx)) {
+            if !matches!(last, Some(l) if compare(l, &v)) {
+                *last = Some(v.clone());
+                return Poll::Ready(Some(v))
+            }
+        }
+        Poll::Ready(None)
+    }
+}
+
+/// Yield a value unless its key equals the key of the last yielded one.
+/// Only the key is kept, so the values need not be Clone.
+#[pin_project(project = DistinctUntilKeyChangedProj)]
+#[must_use = "streams do nothing unless polled"]
+pub struct DistinctUntilKeyChanged<S, F, K> {
+    #[pin]
+    s: S,
+    key_fn: F,
+    last: Option<K>,
+}
+
+impl<S: Stream, F: FnMut(&S::Item) -> K, K: PartialEq> DistinctUntilKeyChanged<S, F, K> {
+    pub fn new(s: S, key_fn: F) -> Self {
+        DistinctUntilKeyChanged {
+            s,
+            key_fn,
+            last: None,
+        }
+    }
+}
+
+impl<S: Stream, F: FnMut(&S::Item) -> K, K: PartialEq> Stream for DistinctUntilKeyChanged<S, F, K> {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let DistinctUntilKeyChangedProj { mut s, key_fn, last } = self.project();
+        while let Some(v) = ready!(s.as_mut().poll_next(cx)) {
+            let key = key_fn(&v);
+            if last.as_ref() != Some(&key) {
+                *last = Some(key);
+                return Poll::Ready(Some(v))
+            }
+        }
+        Poll::Ready(None)
+    }
+}
@@ -0,0 +1,54 @@
+use futures::Stream;
+use futures::stream::{once, Once};
+use std::time::Duration;
+use super::super::super::scheduler::{Scheduler, DefaultScheduler};
+
+/// Make the stream timing the window a value opens, for debounce, throttle
+/// and audit, or how long a group stays idle, for group_by. The window
+/// closes when that stream yields its first value or ends, whichever comes
+/// first.
+pub trait DurationSelector<T> {
+    type Duration: Stream;
+    fn select(&mut self, v: &T) -> Self::Duration;
+}
+
+impl<T, U, F> DurationSelector<T> for F
+    where F: FnMut(&T) -> U, U: Stream
+{
+    type Duration = U;
+
+    fn select(&mut self, v: &T) -> U {
+        self(v)
+    }
+}
+
+/// Windows of a fixed number of milliseconds, which makes the *_time
+/// operators.
+#[derive(Debug, Clone)]
+pub struct TimeSpan<Sch: Scheduler = DefaultScheduler> {
+    span: Duration,
+    scheduler: Sch,
+}
+
+impl TimeSpan {
+    pub fn new(span: u64) -> Self {
+        TimeSpan::new_with_scheduler(span, DefaultScheduler::default())
+    }
+}
+
+impl<Sch: Scheduler> TimeSpan<Sch> {
+    pub fn new_with_scheduler(span: u64, scheduler: Sch) -> Self {
+        TimeSpan {
+            span: Duration::from_millis(span),
+            scheduler,
+        }
+    }
+}
+
+impl<T, Sch: Scheduler> DurationSelector<T> for TimeSpan<Sch> {
+    type Duration = Once<Sch::Delay>;
+
+    fn select(&mut self, _v: &T) -> Self::Duration {
+        once(self.scheduler.delay_until(self.scheduler.now() + self.span))
+    }
+}
@@ -0,0 +1,254 @@
+use futures::task::Context;
+use futures::task::Poll;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use std::fmt;
+use std::pin::Pin;
+use pin_project::pin_project;
+
+/// Why first, last, single or element_at could not pick a value, which is
+/// yielded as Err in place of an rxjs error notification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SequenceError {
+    /// The stream ended without any value.
+    Empty,
+    /// The stream ended before the value at the index.
+    OutOfRange,
+    /// single got a second value.
+    TooMany,
+}
+
+impl fmt::Display for SequenceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SequenceError::Empty => write!(f, "no elements in sequence"),
+            SequenceError::OutOfRange => write!(f, "argument out of range"),
+            SequenceError::TooMany => write!(f, "too many values in sequence"),
+        }
+    }
+}
+
+impl std::error::Error for SequenceError {}
+
+/// Yield the value at index and end, without polling the stream further.
+/// If the stream ends before, yield the error instead.
+#[pin_project(project = ElementAtProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ElementAt<S> {
+    #[pin]
+    s: Fuse<S>,
+    index: usize,
+    error: SequenceError,
+    done: bool,
+}
+
+/// ElementAt of index 0, with SequenceError::Empty.
+pub type First<S> = ElementAt<S>;
+
+impl<S: Stream> ElementAt<S> {
+    pub fn new(s: S, index: usize, error: SequenceError) -> Self {
+        ElementAt {
+            s: s.fuse(),
+            index,
+            error,
+            done: false,
+        }
+    }
+}
+
+impl<S: Stream> Stream for ElementAt<S> {
+    type Item = Result<S::Item, SequenceError>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let ElementAtProj { mut s, index, error, done } = self.project();
+        if *done {
+            return Poll::Ready(None)
+        }
+        while let Some(v) = ready!(s.as_mut().poll_next(cx)) {
+            if *index == 0 {
+                *done = true;
+                return Poll::Ready(Some(Ok(v)))
+            }
+            *index -= 1;
+        }
+        *done = true;
+        Poll::Ready(Some(Err(*error)))
+    }
+}
+
+/// Yield the first value matching the predicate and end, without polling the
+/// stream further. If none matches, yield the default value, or
+/// SequenceError::Empty without one.
+#[pin_project(project = FirstWithProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct FirstWith<S: Stream, P> {
+    #[pin]
+    s: Fuse<S>,
+    predicate: P,
+    default: Option<S::Item>,
+    done: bool,
+}
+
+impl<S: Stream, P: FnMut(&S::Item) -> bool> FirstWith<S, P> {
+    pub fn new(s: S, predicate: P, default: Option<S::Item>) -> Self {
+        FirstWith {
+            s: s.fuse(),
+            predicate,
+            default,
+            done: false,
+        }
+    }
+}
+
+impl<S: Stream, P: FnMut(&S::Item) -> bool> Stream for FirstWith<S, P> {
+    type Item = Result<S::Item, SequenceError>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let FirstWithProj { mut s, predicate, default, done } = self.project();
+        if *done {
+            return Poll::Ready(None)
+        }
+        while let Some(v) = ready!(s.as_mut().poll_next(cx)) {
+            if predicate(&v) {
+                *done = true;
+                return Poll::Ready(Some(Ok(v)))
+            }
+        }
+        *done = true;
+        Poll::Ready(Some(default.take().ok_or(SequenceError::Empty)))
+    }
+}
+
+/// Yield the last value when the stream ends, or SequenceError::Empty.
+#[pin_project(project = LastProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Last<S: Stream> {
+    #[pin]
+    s: Fuse<S>,
+    last: Option<S::Item>,
+}
+
+impl<S: Stream> Last<S> {
+    pub fn new(s: S) -> Self {
+        Last {
+            s: s.fuse(),
+            last: None,
+        }
+    }
+}
+
+impl<S: Stream> Stream for Last<S> {
+    type Item = Result<S::Item, SequenceError>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let LastProj { mut s, last } = self.project();
+        if s.is_done() {
+            return Poll::Ready(None)
+        }
+        while let Some(v) = ready!(s.as_mut().poll_next(cx)) {
+            *last = Some(v);
+        }
+        Poll::Ready(Some(last.take().ok_or(SequenceError::Empty)))
+    }
+}
+
+/// Yield the only value when the stream ends. A second value yields
+/// SequenceError::TooMany right away, and no value SequenceError::Empty.
+#[pin_project(project = SingleProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Single<S: Stream> {
+    #[pin]
+    s: Fuse<S>,
+    value: Option<S::Item>,
+    done: bool,
+}
+
+impl<S: Stream> Single<S> {
+    pub fn new(s: S) -> Self {
+        Single {
+            s: s.fuse(),
+            value: None,
+            done: false,
+        }
+    }
+}
+
+impl<S: Stream> Stream for Single<S> {
+    type Item = Result<S::Item, SequenceError>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let SingleProj { mut s, value, done } = self.project();
+        if *done {
+            return Poll::Ready(None)
+        }
+        while let Some(v) = ready!(s.as_mut().poll_next(cx)) {
+            if value.is_some() {
+                *done = true;
+                return Poll::Ready(Some(Err(SequenceError::TooMany)))
+            }
+            *value = Some(v);
+        }
+        *done = true;
+        Poll::Ready(Some(value.take().ok_or(SequenceError::Empty)))
+    }
+}
+
+/// Yield the last value matching the predicate when the stream ends. If none
+/// matches, yield the default value, or SequenceError::Empty without one.
+#[pin_project(project = LastWithProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct LastWith<S: Stream, P> {
+    #[pin]
+    s: Fuse<S>,
+    predicate: P,
+    last: Option<S::Item>,
+    default: Option<S::Item>,
+}
+
+impl<S: Stream, P: FnMut(&S::Item) -> bool> LastWith<S, P> {
+    pub fn new(s: S, predicate: P, default: Option<S::Item>) -> Self {
+        LastWith {
+            s: s.fuse(),
+            predicate,
+            last: None,
+            default,
+        }
+    }
+}
+
+impl<S: Stream, P: FnMut(&S::Item) -> bool> Stream for LastWith<S, P> {
+    type Item = Result<S::Item, SequenceError>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let LastWithProj { mut s, predicate, last, default } = self.project();
+        if s.is_done() {
+            return Poll::Ready(None)
+        }
+        while let Some(v) = ready!(s.as_mut().poll_next(cx)) {
+            if predicate(&v) {
+                *last = Some(v);
+            }
+        }
+        Poll::Ready(Some(last.take().or_else(|| default.take()).ok_or(SequenceError::Empty)))
+    }
+}
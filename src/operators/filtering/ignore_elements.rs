@@ -0,0 +1,36 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::pin::Pin;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use pin_project::pin_project;
+
+/// Drop every value and end with the stream.
+#[pin_project(project=IgnoreElementsProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct IgnoreElements<S> {
+    #[pin]
+    s: Fuse<S>,
+}
+
+impl<S: Stream> IgnoreElements<S> {
+    pub fn new(s: S) -> Self {
+        IgnoreElements {
+            s: s.fuse(),
+        }
+    }
+}
+
+impl<S: Stream> Stream for IgnoreElements<S> {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut s = self.project().s;
+        while ready!(s.as_mut().poll_next(cx)).is_some() {}
+        Poll::Ready(None)
+    }
+}
@@ -0,0 +1,10 @@
+pub mod duration;
+pub mod debounce;
+pub mod throttle;
+pub mod audit;
+pub mod sample;
+pub mod distinct;
+pub mod element_at;
+pub mod take_until;
+pub mod take_last;
+pub mod ignore_elements;
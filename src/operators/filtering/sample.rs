@@ -0,0 +1,64 @@
+use futures::task::Context;
+use futures::task::Poll;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use std::pin::Pin;
+use pin_project::pin_project;
+use super::super::super::source::TimerStream;
+use super::super::super::scheduler::DefaultScheduler;
+
+/// Whenever the notifier yields, yield the last value if any came since the
+/// previous one. It ends with the stream, and a value waiting for the
+/// notifier is dropped, as in rxjs. The end of the notifier ends nothing,
+/// no value is yielded afterwards though.
+#[pin_project(project = SampleProj)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Sample<S: Stream, N: Stream> {
+    #[pin]
+    s: Fuse<S>,
+    #[pin]
+    notifier: Fuse<N>,
+    last: Option<S::Item>,
+}
+
+pub type SampleTime<S, Sch = DefaultScheduler> = Sample<S, TimerStream<Sch>>;
+
+impl<S: Stream, N: Stream> Sample<S, N> {
+    pub fn new(s: S, notifier: N) -> Self {
+        Sample {
+            s: s.fuse(),
+            notifier: notifier.fuse(),
+            last: None,
+        }
+    }
+}
+
+impl<S: Stream, N: Stream> Stream for Sample<S, N> {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let SampleProj { mut s, mut notifier, last } = self.project();
+        // One value per call, and the notifier only once the stream is
+        // pending, so the values coming at the same time as a tick count
+        match s.as_mut().poll_next(cx) {
+            Poll::Ready(Some(v)) => {
+                *last = Some(v);
+                cx.waker().wake_by_ref();
+                return Poll::Pending
+            }
+            Poll::Ready(None) => return Poll::Ready(None),
+            Poll::Pending => (),
+        }
+        if let Poll::Ready(Some(_)) = notifier.as_mut().poll_next(cx) {
+            if let Some(v) = last.take() {
+                return Poll::Ready(Some(v))
+            }
+            // The notifier hasn't registered the waker, poll it again
+            cx.waker().wake_by_ref();
+        }
+        Poll::Pending
+    }
+}
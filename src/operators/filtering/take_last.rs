@@ -0,0 +1,89 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use pin_project::pin_project;
+
+/// Yield the last count values once the stream ends.
+#[pin_project(project=TakeLastProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct TakeLast<S: Stream> {
+    #[pin]
+    s: Fuse<S>,
+    count: usize,
+    last: VecDeque<S::Item>,
+}
+
+impl<S: Stream> TakeLast<S> {
+    pub fn new(s: S, count: usize) -> Self {
+        TakeLast {
+            s: s.fuse(),
+            count,
+            last: VecDeque::new(),
+        }
+    }
+}
+
+impl<S: Stream> Stream for TakeLast<S> {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let TakeLastProj { mut s, count, last } = self.project();
+        while let Some(v) = ready!(s.as_mut().poll_next(cx)) {
+            if *count == 0 {
+                continue
+            }
+            if last.len() == *count {
+                last.pop_front();
+            }
+            last.push_back(v);
+        }
+        Poll::Ready(last.pop_front())
+    }
+}
+
+/// Yield the values except the last count ones, each one count values late.
+#[pin_project(project=SkipLastProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct SkipLast<S: Stream> {
+    #[pin]
+    s: Fuse<S>,
+    count: usize,
+    queued: VecDeque<S::Item>,
+}
+
+impl<S: Stream> SkipLast<S> {
+    pub fn new(s: S, count: usize) -> Self {
+        SkipLast {
+            s: s.fuse(),
+            count,
+            queued: VecDeque::new(),
+        }
+    }
+}
+
+impl<S: Stream> Stream for SkipLast<S> {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let SkipLastProj { mut s, count, queued } = self.project();
+        while let Some(v) = ready!(s.as_mut().poll_next(cx)) {
+            queued.push_back(v);
+            if queued.len() > *count {
+                return Poll::Ready(queued.pop_front())
+            }
+        }
+        queued.clear();
+        Poll::Ready(None)
+    }
+}
@@ -0,0 +1,94 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::pin::Pin;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use pin_project::pin_project;
+
+/// Yield the values until the notifier yields, then end. The end of the
+/// notifier without a value ends nothing, as in rxjs.
+#[pin_project(project=TakeUntilProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct TakeUntil<S, N> {
+    #[pin]
+    source: Fuse<S>,
+    #[pin]
+    notifier: Fuse<N>,
+    stopped: bool,
+}
+
+impl<S: Stream, N: Stream> TakeUntil<S, N> {
+    pub fn new(source: S, notifier: N) -> Self {
+        TakeUntil {
+            source: source.fuse(),
+            notifier: notifier.fuse(),
+            stopped: false,
+        }
+    }
+}
+
+impl<S: Stream, N: Stream> Stream for TakeUntil<S, N> {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let TakeUntilProj { source, mut notifier, stopped } = self.project();
+        if !*stopped {
+            if let Poll::Ready(Some(_)) = notifier.as_mut().poll_next(cx) {
+                *stopped = true;
+            }
+        }
+        if *stopped {
+            return Poll::Ready(None)
+        }
+        source.poll_next(cx)
+    }
+}
+
+/// Drop the values until the notifier yields, then yield them all. If the
+/// notifier ends without a value, every value is dropped, as in rxjs.
+#[pin_project(project=SkipUntilProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct SkipUntil<S, N> {
+    #[pin]
+    source: Fuse<S>,
+    #[pin]
+    notifier: Fuse<N>,
+    started: bool,
+}
+
+impl<S: Stream, N: Stream> SkipUntil<S, N> {
+    pub fn new(source: S, notifier: N) -> Self {
+        SkipUntil {
+            source: source.fuse(),
+            notifier: notifier.fuse(),
+            started: false,
+        }
+    }
+}
+
+impl<S: Stream, N: Stream> Stream for SkipUntil<S, N> {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let SkipUntilProj { mut source, mut notifier, started } = self.project();
+        if !*started {
+            if let Poll::Ready(Some(_)) = notifier.as_mut().poll_next(cx) {
+                *started = true;
+            }
+        }
+        if *started {
+            return source.poll_next(cx)
+        }
+        // Drain the source, so it registers the waker once it's pending
+        while ready!(source.as_mut().poll_next(cx)).is_some() {}
+        Poll::Ready(None)
+    }
+}
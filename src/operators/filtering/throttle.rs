@@ -0,0 +1,103 @@
+use futures::task::Context;
+use futures::task::Poll;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use std::pin::Pin;
+use pin_project::pin_project;
+use super::duration::{DurationSelector, TimeSpan};
+use super::super::super::scheduler::DefaultScheduler;
+
+/// Which values of a window throttle yields. The default is leading only,
+/// as in rxjs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThrottleConfig {
+    /// Yield the value opening a window.
+    pub leading: bool,
+    /// Yield the last value seen in a window when it closes, which opens
+    /// the next window.
+    pub trailing: bool,
+}
+
+impl Default for ThrottleConfig {
+    fn default() -> Self {
+        ThrottleConfig { leading: true, trailing: false }
+    }
+}
+
+/// A value coming while no window is open opens one, and the values coming
+/// in the window are dropped, except the last one when trailing is set.
+/// When the stream ends in a window with a trailing value, the window still
+/// runs to yield it.
+#[pin_project(project = ThrottleProj)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Throttle<S: Stream, F: DurationSelector<S::Item>> {
+    #[pin]
+    s: Fuse<S>,
+    #[pin]
+    window: Option<F::Duration>,
+    trailing: Option<S::Item>,
+    selector: F,
+    config: ThrottleConfig,
+}
+
+pub type ThrottleTime<S, Sch = DefaultScheduler> = Throttle<S, TimeSpan<Sch>>;
+
+impl<S: Stream, F: DurationSelector<S::Item>> Throttle<S, F> {
+    pub fn new(s: S, selector: F, config: ThrottleConfig) -> Self {
+        Throttle {
+            s: s.fuse(),
+            window: None,
+            trailing: None,
+            selector,
+            config,
+        }
+    }
+}
+
+impl<S: Stream, F: DurationSelector<S::Item>> Stream for Throttle<S, F> {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let ThrottleProj { mut s, mut window, trailing, selector, config } = self.project();
+        loop {
+            if let Some(w) = window.as_mut().as_pin_mut() {
+                if w.poll_next(cx).is_ready() {
+                    window.set(None);
+                    if let Some(v) = trailing.take() {
+                        window.set(Some(selector.select(&v)));
+                        return Poll::Ready(Some(v))
+                    }
+                }
+            }
+            if s.is_done() {
+                return if window.is_some() && trailing.is_some() {
+                    Poll::Pending
+                } else {
+                    Poll::Ready(None)
+                }
+            }
+            match s.as_mut().poll_next(cx) {
+                Poll::Ready(Some(v)) => {
+                    if window.is_none() {
+                        window.set(Some(selector.select(&v)));
+                        if config.leading {
+                            return Poll::Ready(Some(v))
+                        }
+                    }
+                    if config.trailing {
+                        *trailing = Some(v);
+                    }
+                    // Looping on would never end with an always ready stream
+                    cx.waker().wake_by_ref();
+                    return Poll::Pending
+                }
+                // Loop to check whether a trailing value is left
+                Poll::Ready(None) => (),
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
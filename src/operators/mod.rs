@@ -1,17 +1,83 @@
 use std::pin::Pin;
-use futures::{Stream, StreamExt, FutureExt};
-use futures::stream::Select;
+use futures::{Stream, TryStream};
+use futures::stream::{Pending, Select};
+use std::hash::Hash;
 mod combination;
+mod error_handling;
+mod filtering;
+mod multicasting;
 mod transform;
 pub use transform::pairwise::Pairwise;
 pub use combination::combine_latest::CombineLatest;
-pub use combination::combine_latest::CombineLatestVec;
+pub use combination::combine_latest::{CombineAll, CombineLatestVec};
+pub use combination::concat::{ChainVec, ConcatAll};
+pub use combination::combine_latest::{
+    CombineLatest3, CombineLatest4, CombineLatest5, CombineLatest6, CombineLatest7,
+    CombineLatest8, CombineLatest9, CombineLatest10, CombineLatest11, CombineLatest12,
+};
+pub use combination::fork_join::{
+    ForkJoin, ForkJoin3, ForkJoin4, ForkJoin5, ForkJoin6, ForkJoin7,
+    ForkJoin8, ForkJoin9, ForkJoin10, ForkJoin11, ForkJoin12,
+};
+pub use combination::merge::{MergeAll, MergeVec};
+pub use combination::zip::{EndOnFirst, PadWithNone, ZipAll, ZipPolicy, ZipVec};
+pub use combination::zip::{
+    Zip3, Zip4, Zip5, Zip6, Zip7, Zip8, Zip9, Zip10, Zip11, Zip12,
+};
+pub use combination::merge::{
+    Merge3, Merge4, Merge5, Merge6, Merge7, Merge8, Merge9, Merge10, Merge11, Merge12,
+};
+pub use combination::race::{
+    Race, Race3, Race4, Race5, Race6, Race7, Race8, Race9, Race10, Race11, Race12,
+    RaceAll, RaceVec,
+};
 pub use combination::with_latest_from::WithLatestFrom;
+pub use combination::with_latest_from::{
+    WithLatestFrom2, WithLatestFrom3, WithLatestFrom4, WithLatestFrom5, WithLatestFrom6,
+    WithLatestFrom7, WithLatestFrom8, WithLatestFrom9, WithLatestFrom10, WithLatestFrom11,
+};
 pub use transform::simple_count_buffer::SimpleCountBufferedStream;
 pub use transform::overlapped_count_buffer::OverlappedCountBufferedStream;
 pub use transform::simple_time_buffer::{SimpleExternalTimeBufferredStream, SimpleTimeBufferredStream};
 pub use transform::overlapped_time_buffer::OverlappedTimeBufferedStream;
+pub use transform::notifier_buffer::{NotifierBufferedStream, WhenBufferedStream};
+pub use transform::toggle_buffer::ToggleBufferedStream;
+pub use transform::window::{
+    Window, Windows, WindowedStream, CountWindowedStream, OverlappedCountWindowedStream, TimeWindowedStream,
+    OverlappedTimeWindowedStream, NotifierWindowedStream, ToggleWindowedStream,
+};
+pub use transform::switch_map::SwitchMap;
+pub use transform::merge_map::{ConcatMap, MergeMap};
+pub use transform::exhaust_map::ExhaustMap;
+pub use transform::scan::{Reduce, ReduceSeedless, Scan, ScanSeedless};
+pub use transform::merge_scan::MergeScan;
+pub use transform::expand::Expand;
+pub use transform::group_by::{GroupBy, GroupedStream, NoDuration, SameElement, SimpleGroupBy};
+pub use multicasting::share::{Published, RefCount, Shared};
+pub use multicasting::partition::Partition;
+pub use filtering::duration::{DurationSelector, TimeSpan};
+pub use filtering::debounce::{Debounce, DebounceTime};
+pub use filtering::throttle::{Throttle, ThrottleConfig, ThrottleTime};
+pub use filtering::audit::{Audit, AuditTime};
+pub use filtering::sample::{Sample, SampleTime};
+pub use filtering::distinct::{
+    Distinct, DistinctByValue, DistinctUntilChanged, DistinctUntilKeyChanged, DistinctUntilValueChanged,
+};
+pub use filtering::element_at::{ElementAt, First, FirstWith, Last, LastWith, SequenceError, Single};
+pub use filtering::take_until::{SkipUntil, TakeUntil};
+pub use filtering::take_last::{SkipLast, TakeLast};
+pub use filtering::ignore_elements::IgnoreElements;
+pub use error_handling::short_circuit::{ErrorSlot, ShortCircuit, TakeOks};
+pub use error_handling::catch_error::{CatchError, OnErrorResumeNext};
+pub use error_handling::retry::{Backoff, BackoffDelay, BackoffNotifier, Retry, RetryCount, RetryNotifier};
+pub use error_handling::short_circuit::{
+    TryCombineLatest, TryForkJoin, TryWithLatestFrom, TryRace, TryPairwise,
+    TrySimpleCountBufferedStream, TryOverlappedCountBufferedStream,
+    TrySimpleTimeBufferredStream, TrySimpleExternalTimeBufferredStream,
+    TryOverlappedTimeBufferedStream,
+};
 use super::source;
+use super::scheduler::{Scheduler, DefaultScheduler};
 
 // static operators
 
@@ -26,51 +92,107 @@ use super::source;
 pub use combination::combine_latest::combine_latest;
 pub use combination::combine_latest::combine_latest_vec;
 
-/// combine_all
+/// combine latest of 3 to 12 streams of different types, yielding flat tuples.
+/// Same notes as combine_latest apply.
+pub use combination::combine_latest::{
+    combine_latest3, combine_latest4, combine_latest5, combine_latest6, combine_latest7,
+    combine_latest8, combine_latest9, combine_latest10, combine_latest11, combine_latest12,
+};
+
+/// Combine latest of the inner streams as they come from the outer stream.
 /// See warning in combine_latest as this one use same logic there. 
-pub fn combine_all<SInner: Stream, SOuter: Stream<Item=SInner>>(s: SOuter) -> 
-    impl Stream<Item=Vec<SInner::Item>> 
-    where SInner::Item: Clone
-{
-    s.collect()
-        .map(|streams| combine_latest_vec(streams))
-        .flatten_stream()
-}
+pub use combination::combine_latest::combine_all;
 
 /// merge is an alias of select operator in rust stream library. 
 /// Notes 
 /// 1. merge in rust stream library is a deprecated operator, and replaced by select. 
-/// 2. The 'concurrent' parameter is not supported due to we only support two operands.
+/// 2. For the 'concurrent' parameter, see merge_vec_with_concurrency.
 pub fn merge<S1: Stream, S2: Stream<Item=S1::Item>>(s1: S1, s2: S2) -> Select<S1, S2> {
     futures::stream::select(s1, s2)
 }
 
+pub use combination::merge::{merge_vec, merge_vec_with_concurrency};
+pub use combination::merge::{merge_all, merge_all_with_concurrency};
+
+/// merge 3 to 12 streams of different types with the same item type.
+pub use combination::merge::{
+    merge3, merge4, merge5, merge6, merge7, merge8, merge9, merge10, merge11, merge12,
+};
+
 /// concat is an alias of chain operator in rust. 
 /// Notes 
 /// 1. concat in rust stream library means a totally different thing. Do not confuse with
-/// the concat here which follows the rxjs naming convension. 
+///    the concat here which follows the rxjs naming convension. 
 pub use combination::concat::concat;
 pub use combination::concat::concat_vec;
+/// Concat the inner streams as they come from the outer stream.
 pub use combination::concat::concat_all;
 
 /// run both stream to the end, and yield the tuple of both stream's last value as value then end. 
 /// Notes
-/// 1. The error type is either of the error happened. 
+/// 1. Items are plain values here. For streams of Result, try_fork_join in 
+///    RxTryStreamEx yields the error of whichever stream fails first.
 /// 2. If any of the stream end without value, the result stream will be empty (end without value)
 pub use combination::fork_join::fork_join;
 
-/// merge two streams
+/// fork_join of 3 to 12 streams of different types, yielding a flat tuple.
+/// Same notes as fork_join apply, and it ends as soon as any stream ends without value.
+pub use combination::fork_join::{
+    fork_join3, fork_join4, fork_join5, fork_join6, fork_join7,
+    fork_join8, fork_join9, fork_join10, fork_join11, fork_join12,
+};
+
+/// zip two streams, yielding pairs of their n-th values. It ends when either ends.
 pub use futures::stream::Zip;
 
-/// Pick the first stream respond. 
+pub fn zip<S1: Stream, S2: Stream>(s1: S1, s2: S2) -> Zip<S1, S2> {
+    futures::stream::StreamExt::zip(s1, s2)
+}
+
+/// zip of 3 to 12 streams of different types, yielding flat tuples. It ends
+/// as soon as any stream ends.
+pub use combination::zip::{
+    zip3, zip4, zip5, zip6, zip7, zip8, zip9, zip10, zip11, zip12,
+};
+
+/// zip any number of streams, see ZipPolicy for when it completes.
+pub use combination::zip::{zip_vec, zip_vec_with_policy, zip_all, zip_all_with_policy};
+
+/// Pick the first stream to respond and drop the other. The streams can be
+/// of different types with the same item type.
 pub use combination::race::race;
 
+/// race of 3 to 12 streams of different types with the same item type.
+pub use combination::race::{
+    race3, race4, race5, race6, race7, race8, race9, race10, race11, race12,
+};
+
+/// race any number of streams, or the inner streams of a stream.
+pub use combination::race::{race_vec, race_all};
+
+/// Run the fallible streams one after another, moving on to the next one
+/// whenever the current one ends or yields an error. The errors are dropped,
+/// so the result yields the Ok values only.
+pub use error_handling::catch_error::on_error_resume_next;
+
 pub fn start_with<S: Stream, V: IntoIterator<Item=S::Item>>(v: V, s: S) -> impl Stream<Item=S::Item> {
     concat(source::of(v), s)
 }
 
 impl<T> RxStreamEx for T where T: Stream {}
 
+/// with_latest_from taking several other streams, see WithLatestFromN
+macro_rules! with_latest_from_n_fn {
+    ($fn_name:ident, $name:ident, $(($O:ident, $other:ident)),+) => {
+        #[allow(clippy::too_many_arguments)]
+        fn $fn_name<$($O: Stream),+>(self, $($other: $O),+) -> $name<Self, $($O),+>
+            where Self: Sized, $($O::Item: Clone),+
+        {
+            $name::new(self, $($other),+)
+        }
+    };
+}
+
 // function operators
 pub trait RxStreamEx: Stream {
     fn pairwise(self) -> Pairwise<Self> 
@@ -85,6 +207,210 @@ pub trait RxStreamEx: Stream {
         WithLatestFrom::new(self, other)
     }   
 
+    with_latest_from_n_fn!(with_latest_from2, WithLatestFrom2,
+        (O1, other1), (O2, other2));
+    with_latest_from_n_fn!(with_latest_from3, WithLatestFrom3,
+        (O1, other1), (O2, other2), (O3, other3));
+    with_latest_from_n_fn!(with_latest_from4, WithLatestFrom4,
+        (O1, other1), (O2, other2), (O3, other3), (O4, other4));
+    with_latest_from_n_fn!(with_latest_from5, WithLatestFrom5,
+        (O1, other1), (O2, other2), (O3, other3), (O4, other4), (O5, other5));
+    with_latest_from_n_fn!(with_latest_from6, WithLatestFrom6,
+        (O1, other1), (O2, other2), (O3, other3), (O4, other4), (O5, other5), (O6, other6));
+    with_latest_from_n_fn!(with_latest_from7, WithLatestFrom7,
+        (O1, other1), (O2, other2), (O3, other3), (O4, other4), (O5, other5), (O6, other6),
+        (O7, other7));
+    with_latest_from_n_fn!(with_latest_from8, WithLatestFrom8,
+        (O1, other1), (O2, other2), (O3, other3), (O4, other4), (O5, other5), (O6, other6),
+        (O7, other7), (O8, other8));
+    with_latest_from_n_fn!(with_latest_from9, WithLatestFrom9,
+        (O1, other1), (O2, other2), (O3, other3), (O4, other4), (O5, other5), (O6, other6),
+        (O7, other7), (O8, other8), (O9, other9));
+    with_latest_from_n_fn!(with_latest_from10, WithLatestFrom10,
+        (O1, other1), (O2, other2), (O3, other3), (O4, other4), (O5, other5), (O6, other6),
+        (O7, other7), (O8, other8), (O9, other9), (O10, other10));
+    with_latest_from_n_fn!(with_latest_from11, WithLatestFrom11,
+        (O1, other1), (O2, other2), (O3, other3), (O4, other4), (O5, other5), (O6, other6),
+        (O7, other7), (O8, other8), (O9, other9), (O10, other10), (O11, other11));
+
+    /// Map each value to an inner stream and yield from the latest one,
+    /// dropping the previous inner stream. It ends when both the outer
+    /// stream and the current inner stream end.
+    fn switch_map<U: Stream, F: FnMut(Self::Item) -> U>(self, f: F) -> SwitchMap<Self, F, U>
+        where Self: Sized
+    {
+        SwitchMap::new(self, f)
+    }
+
+    /// Map each value to an inner stream and yield from all of them as
+    /// values come.
+    fn merge_map<U: Stream, F: FnMut(Self::Item) -> U>(self, f: F) -> MergeMap<Self, F, U>
+        where Self: Sized
+    {
+        MergeMap::new(self, f, usize::MAX)
+    }
+
+    /// merge_map running at most concurrency inner streams at a time. The
+    /// outer stream is not polled while the limit is reached.
+    fn merge_map_with_concurrency<U: Stream, F: FnMut(Self::Item) -> U>(self, f: F, concurrency: usize) -> MergeMap<Self, F, U>
+        where Self: Sized
+    {
+        MergeMap::new(self, f, concurrency)
+    }
+
+    /// Map each value to an inner stream and yield from them one after another.
+    fn concat_map<U: Stream, F: FnMut(Self::Item) -> U>(self, f: F) -> ConcatMap<Self, F, U>
+        where Self: Sized
+    {
+        MergeMap::new(self, f, 1)
+    }
+
+    /// Map a value to an inner stream only when no inner stream is running,
+    /// the values coming meanwhile are dropped.
+    fn exhaust_map<U: Stream, F: FnMut(Self::Item) -> U>(self, f: F) -> ExhaustMap<Self, F, U>
+        where Self: Sized
+    {
+        ExhaustMap::new(self, f)
+    }
+
+    /// Yield the accumulator after folding each value into it, starting
+    /// from seed. Unlike StreamExt::scan, the function returns the new
+    /// accumulator instead of a future. With both traits in scope, call it
+    /// as RxStreamEx::scan(s, seed, f).
+    fn scan<A: Clone, F: FnMut(A, Self::Item) -> A>(self, seed: A, f: F) -> Scan<Self, F, A>
+        where Self: Sized
+    {
+        Scan::new(self, seed, f)
+    }
+
+    /// scan using the first value as the seed, which is yielded as is.
+    fn scan_seedless<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> ScanSeedless<Self, F>
+        where Self: Sized, Self::Item: Clone
+    {
+        ScanSeedless::new(self, f)
+    }
+
+    /// Yield only the final accumulator of scan once the stream ends, the
+    /// seed if it is empty.
+    fn reduce<A, F: FnMut(A, Self::Item) -> A>(self, seed: A, f: F) -> Reduce<Self, F, A>
+        where Self: Sized
+    {
+        Reduce::new(self, seed, f)
+    }
+
+    /// reduce using the first value as the seed. An empty stream ends
+    /// without a value.
+    fn reduce_seedless<F: FnMut(Self::Item, Self::Item) -> Self::Item>(self, f: F) -> ReduceSeedless<Self, F>
+        where Self: Sized
+    {
+        ReduceSeedless::new(self, f)
+    }
+
+    /// Map each value with the accumulator to an inner stream, yield from
+    /// all of them and make each yielded value the accumulator.
+    fn merge_scan<A: Clone, U: Stream<Item = A>, F: FnMut(A, Self::Item) -> U>(self, seed: A, f: F) -> MergeScan<Self, F, A, U>
+        where Self: Sized
+    {
+        MergeScan::new(self, seed, f, usize::MAX)
+    }
+
+    /// merge_scan running at most concurrency inner streams at a time.
+    fn merge_scan_with_concurrency<A: Clone, U: Stream<Item = A>, F: FnMut(A, Self::Item) -> U>(self, seed: A, f: F, concurrency: usize) -> MergeScan<Self, F, A, U>
+        where Self: Sized
+    {
+        MergeScan::new(self, seed, f, concurrency)
+    }
+
+    /// Yield each value and map it to an inner stream, whose values are
+    /// yielded and mapped in turn, recursively. The stream ends once no
+    /// inner stream is left, so f must eventually return empty streams.
+    fn expand<U: Stream<Item = Self::Item>, F: FnMut(Self::Item) -> U>(self, f: F) -> Expand<Self, F, U>
+        where Self: Sized, Self::Item: Clone
+    {
+        Expand::new(self, f, usize::MAX)
+    }
+
+    /// expand running at most concurrency inner streams at a time, the
+    /// values waiting for one are queued.
+    fn expand_with_concurrency<U: Stream<Item = Self::Item>, F: FnMut(Self::Item) -> U>(self, f: F, concurrency: usize) -> Expand<Self, F, U>
+        where Self: Sized, Self::Item: Clone
+    {
+        Expand::new(self, f, concurrency)
+    }
+
+    /// Split the values into a stream per key, see GroupBy. A group holds
+    /// one value at a time and the upstream waits until it is taken, so the
+    /// groups must be polled along with the outer stream:
+    /// `group_by(..).collect()` never ends once a group gets a value, while
+    /// `group_by(..).merge_map(..)` does.
+    fn group_by<K, F>(self, key_selector: F) -> SimpleGroupBy<Self, K, F>
+        where Self: Sized,
+              K: Eq + Hash + Clone,
+              F: FnMut(&Self::Item) -> K,
+    {
+        GroupBy::new(self, key_selector, |v| v, |_| futures::stream::pending())
+    }
+
+    /// group_by yielding element_selector of each value in the groups.
+    fn group_by_with_element<K, V, F, E>(self, key_selector: F, element_selector: E) -> GroupBy<Self, K, V, F, E, NoDuration<K>>
+        where Self: Sized,
+              K: Eq + Hash + Clone,
+              F: FnMut(&Self::Item) -> K,
+              E: FnMut(Self::Item) -> V,
+    {
+        GroupBy::new(self, key_selector, element_selector, |_| futures::stream::pending())
+    }
+
+    /// group_by closing a group once it gets no value for the duration
+    /// selected for its key, which restarts with each value of the group.
+    fn group_by_with_duration<K, F, D>(self, key_selector: F, duration_selector: D) -> GroupBy<Self, K, Self::Item, F, SameElement<Self::Item>, D>
+        where Self: Sized,
+              K: Eq + Hash + Clone,
+              F: FnMut(&Self::Item) -> K,
+              D: DurationSelector<K>,
+    {
+        GroupBy::new(self, key_selector, |v| v, duration_selector)
+    }
+
+    fn group_by_with_element_and_duration<K, V, F, E, D>(self, key_selector: F, element_selector: E, duration_selector: D) -> GroupBy<Self, K, V, F, E, D>
+        where Self: Sized,
+              K: Eq + Hash + Clone,
+              F: FnMut(&Self::Item) -> K,
+              E: FnMut(Self::Item) -> V,
+              D: DurationSelector<K>,
+    {
+        GroupBy::new(self, key_selector, element_selector, duration_selector)
+    }
+
+    /// Share one subscription to this stream among the clones of the
+    /// returned handle, see Shared.
+    fn share(self) -> Shared<Self>
+        where Self: Sized, Self::Item: Clone
+    {
+        Shared::share(self)
+    }
+
+    fn share_replay(self, buffer_size: usize) -> Shared<Self>
+        where Self: Sized, Self::Item: Clone
+    {
+        Shared::share_replay(self, buffer_size)
+    }
+
+    /// Multicast this stream to the handles subscribed before connect.
+    fn publish(self) -> Published<Self>
+        where Self: Sized, Self::Item: Clone
+    {
+        Published::new(self)
+    }
+
+    /// Split the stream into the values matching the predicate and the
+    /// others, see Partition.
+    fn partition<P: FnMut(&Self::Item) -> bool>(self, predicate: P) -> (Partition<Self, P>, Partition<Self, P>)
+        where Self: Sized
+    {
+        Partition::new(self, predicate)
+    }
+
     fn buffer_count(self, count: usize) -> SimpleCountBufferedStream<Self> 
         where Self: Sized
     {
@@ -103,7 +429,16 @@ pub trait RxStreamEx: Stream {
         SimpleTimeBufferredStream::new(self, time_span)
     }
 
-    fn buffer_time_with_external_timer(self, timer_stream: Pin<&mut source::TimerStream>) -> SimpleExternalTimeBufferredStream<Self> 
+    fn buffer_time_with_scheduler<Sch: Scheduler>(self, time_span: u64, scheduler: Sch) -> SimpleTimeBufferredStream<Self, Sch>
+        where Self: Sized
+    {
+        SimpleTimeBufferredStream::new_with_scheduler(self, time_span, scheduler)
+    }
+
+    fn buffer_time_with_external_timer<Sch: Scheduler>(
+        self,
+        timer_stream: Pin<&mut source::TimerStream<Sch>>,
+    ) -> SimpleExternalTimeBufferredStream<'_, Self, Sch> 
         where Self: Sized
     {
         SimpleExternalTimeBufferredStream::new_with_timer_stream(self, timer_stream)
@@ -119,4 +454,488 @@ pub trait RxStreamEx: Stream {
         OverlappedTimeBufferedStream::new(self, time_span, creation_interval)
     }
 
-}
\ No newline at end of file
+    fn buffer_time_with_creation_interval_and_scheduler<Sch: Scheduler>(
+        self,
+        time_span: u64,
+        creation_interval: u64,
+        scheduler: Sch,
+    ) -> OverlappedTimeBufferedStream<Self, Sch>
+        where Self: Sized, Self::Item: Clone
+    {
+        OverlappedTimeBufferedStream::new_with_scheduler(self, time_span, creation_interval, scheduler)
+    }
+
+    /// Yield the values buffered so far whenever the notifier yields, and
+    /// the values left when the stream ends.
+    fn buffer<N: Stream>(self, notifier: N) -> NotifierBufferedStream<Self, N>
+        where Self: Sized
+    {
+        NotifierBufferedStream::new(self, notifier)
+    }
+
+    /// Open a buffer whenever openings yields, which is closed and yielded
+    /// when the stream made from the opening value yields. The buffers may
+    /// overlap, and the ones still open are yielded when the stream ends.
+    fn buffer_toggle<O: Stream, C: Stream, F: FnMut(O::Item) -> C>(
+        self,
+        openings: O,
+        closing_selector: F,
+    ) -> ToggleBufferedStream<Self, O, F, C>
+        where Self: Sized, Self::Item: Clone
+    {
+        ToggleBufferedStream::new(self, openings, closing_selector)
+    }
+
+    /// Buffer the values until the stream made by closing_selector yields,
+    /// then start the next buffer with a new closing stream.
+    fn buffer_when<C: Stream, F: FnMut() -> C>(self, closing_selector: F) -> WhenBufferedStream<Self, F, C>
+        where Self: Sized
+    {
+        WhenBufferedStream::new(self, closing_selector)
+    }
+
+    /// Same windows as buffer_count, each yielded as a stream as soon as it
+    /// opens, see WindowedStream. The window_* operators need Clone items,
+    /// as the windows are subjects.
+    fn window_count(self, count: usize) -> CountWindowedStream<Self>
+        where Self: Sized, Self::Item: Clone
+    {
+        CountWindowedStream::new(self, count)
+    }
+
+    fn window_count_with_skip(self, count: usize, skip: usize) -> OverlappedCountWindowedStream<Self>
+        where Self: Sized, Self::Item: Clone
+    {
+        OverlappedCountWindowedStream::new(self, count, skip)
+    }
+
+    fn window_time(self, time_span: u64) -> TimeWindowedStream<Self>
+        where Self: Sized, Self::Item: Clone
+    {
+        TimeWindowedStream::new(self, time_span)
+    }
+
+    fn window_time_with_scheduler<Sch: Scheduler>(self, time_span: u64, scheduler: Sch) -> TimeWindowedStream<Self, Sch>
+        where Self: Sized, Self::Item: Clone
+    {
+        TimeWindowedStream::new_with_scheduler(self, time_span, scheduler)
+    }
+
+    fn window_time_with_creation_interval(
+        self,
+        time_span: u64,
+        creation_interval: u64,
+    ) -> OverlappedTimeWindowedStream<Self>
+        where Self: Sized, Self::Item: Clone
+    {
+        OverlappedTimeWindowedStream::new(self, time_span, creation_interval)
+    }
+
+    fn window_time_with_creation_interval_and_scheduler<Sch: Scheduler>(
+        self,
+        time_span: u64,
+        creation_interval: u64,
+        scheduler: Sch,
+    ) -> OverlappedTimeWindowedStream<Self, Sch>
+        where Self: Sized, Self::Item: Clone
+    {
+        OverlappedTimeWindowedStream::new_with_scheduler(self, time_span, creation_interval, scheduler)
+    }
+
+    fn window<N: Stream>(self, notifier: N) -> NotifierWindowedStream<Self, N>
+        where Self: Sized, Self::Item: Clone
+    {
+        NotifierWindowedStream::new(self, notifier)
+    }
+
+    fn window_toggle<O: Stream, C: Stream, F: FnMut(O::Item) -> C>(
+        self,
+        openings: O,
+        closing_selector: F,
+    ) -> ToggleWindowedStream<Self, O, F, C>
+        where Self: Sized, Self::Item: Clone
+    {
+        ToggleWindowedStream::new(self, openings, closing_selector)
+    }
+
+    /// Yield a value only once due_time passed without another value.
+    fn debounce_time(self, due_time: u64) -> DebounceTime<Self>
+        where Self: Sized
+    {
+        Debounce::new(self, TimeSpan::new(due_time))
+    }
+
+    fn debounce_time_with_scheduler<Sch: Scheduler>(self, due_time: u64, scheduler: Sch) -> DebounceTime<Self, Sch>
+        where Self: Sized
+    {
+        Debounce::new(self, TimeSpan::new_with_scheduler(due_time, scheduler))
+    }
+
+    /// Yield a value only once the stream made from it yields or ends
+    /// without another value coming, see DurationSelector.
+    fn debounce<F: DurationSelector<Self::Item>>(self, selector: F) -> Debounce<Self, F>
+        where Self: Sized
+    {
+        Debounce::new(self, selector)
+    }
+
+    /// Yield a value, then drop the values coming within duration after it.
+    fn throttle_time(self, duration: u64) -> ThrottleTime<Self>
+        where Self: Sized
+    {
+        Throttle::new(self, TimeSpan::new(duration), ThrottleConfig::default())
+    }
+
+    /// throttle_time yielding the leading and/or the trailing value of each window.
+    fn throttle_time_with_config(self, duration: u64, config: ThrottleConfig) -> ThrottleTime<Self>
+        where Self: Sized
+    {
+        Throttle::new(self, TimeSpan::new(duration), config)
+    }
+
+    fn throttle_time_with_config_and_scheduler<Sch: Scheduler>(
+        self,
+        duration: u64,
+        config: ThrottleConfig,
+        scheduler: Sch,
+    ) -> ThrottleTime<Self, Sch>
+        where Self: Sized
+    {
+        Throttle::new(self, TimeSpan::new_with_scheduler(duration, scheduler), config)
+    }
+
+    /// Yield a value, then drop the values coming until the stream made
+    /// from it yields or ends.
+    fn throttle<F: DurationSelector<Self::Item>>(self, selector: F) -> Throttle<Self, F>
+        where Self: Sized
+    {
+        Throttle::new(self, selector, ThrottleConfig::default())
+    }
+
+    fn throttle_with_config<F: DurationSelector<Self::Item>>(self, selector: F, config: ThrottleConfig) -> Throttle<Self, F>
+        where Self: Sized
+    {
+        Throttle::new(self, selector, config)
+    }
+
+    /// Wait duration after a value, then yield the last value seen meanwhile.
+    fn audit_time(self, duration: u64) -> AuditTime<Self>
+        where Self: Sized
+    {
+        Audit::new(self, TimeSpan::new(duration))
+    }
+
+    fn audit_time_with_scheduler<Sch: Scheduler>(self, duration: u64, scheduler: Sch) -> AuditTime<Self, Sch>
+        where Self: Sized
+    {
+        Audit::new(self, TimeSpan::new_with_scheduler(duration, scheduler))
+    }
+
+    /// Wait until the stream made from a value yields or ends, then yield
+    /// the last value seen meanwhile.
+    fn audit<F: DurationSelector<Self::Item>>(self, selector: F) -> Audit<Self, F>
+        where Self: Sized
+    {
+        Audit::new(self, selector)
+    }
+
+    /// Yield the last value every period, if a value came since the last one.
+    fn sample_time(self, period: u64) -> SampleTime<Self>
+        where Self: Sized
+    {
+        Sample::new(self, source::interval(period))
+    }
+
+    fn sample_time_with_scheduler<Sch: Scheduler>(self, period: u64, scheduler: Sch) -> SampleTime<Self, Sch>
+        where Self: Sized
+    {
+        Sample::new(self, source::interval_with_scheduler(period, scheduler))
+    }
+
+    /// Yield the last value whenever the notifier yields, if a value came
+    /// since the last one.
+    fn sample<N: Stream>(self, notifier: N) -> Sample<Self, N>
+        where Self: Sized
+    {
+        Sample::new(self, notifier)
+    }
+
+    /// Yield the values not seen before.
+    fn distinct(self) -> DistinctByValue<Self>
+        where Self: Sized, Self::Item: Clone + Eq + Hash
+    {
+        Distinct::new(self, Self::Item::clone, futures::stream::pending())
+    }
+
+    /// Yield the values whose key was not seen before.
+    fn distinct_by_key<K: Eq + Hash, F: FnMut(&Self::Item) -> K>(self, key_fn: F) -> Distinct<Self, F, K, Pending<()>>
+        where Self: Sized
+    {
+        Distinct::new(self, key_fn, futures::stream::pending())
+    }
+
+    /// distinct_by_key forgetting the seen keys whenever flush yields, so
+    /// the set doesn't grow forever on long streams.
+    fn distinct_with_flush<K: Eq + Hash, F: FnMut(&Self::Item) -> K, N: Stream>(
+        self,
+        key_fn: F,
+        flush: N,
+    ) -> Distinct<Self, F, K, N>
+        where Self: Sized
+    {
+        Distinct::new(self, key_fn, flush)
+    }
+
+    /// Yield a value unless it equals the last yielded one.
+    fn distinct_until_changed(self) -> DistinctUntilValueChanged<Self>
+        where Self: Sized, Self::Item: Clone + PartialEq
+    {
+        DistinctUntilChanged::new(self, Self::Item::eq)
+    }
+
+    /// distinct_until_changed telling equal values by compare.
+    fn distinct_until_changed_with<F: FnMut(&Self::Item, &Self::Item) -> bool>(self, compare: F) -> DistinctUntilChanged<Self, F>
+        where Self: Sized, Self::Item: Clone
+    {
+        DistinctUntilChanged::new(self, compare)
+    }
+
+    /// Yield a value unless its key equals the key of the last yielded one.
+    fn distinct_until_key_changed<K: PartialEq, F: FnMut(&Self::Item) -> K>(self, key_fn: F) -> DistinctUntilKeyChanged<Self, F, K>
+        where Self: Sized
+    {
+        DistinctUntilKeyChanged::new(self, key_fn)
+    }
+
+    /// Yield Ok of the first value and end, or Err(SequenceError::Empty).
+    /// Use StreamExt::take(1) to end quietly on an empty stream instead.
+    fn first(self) -> First<Self>
+        where Self: Sized
+    {
+        ElementAt::new(self, 0, SequenceError::Empty)
+    }
+
+    /// Like first, but only over values matching the predicate, as rxjs
+    /// first(predicate, defaultValue): with no match yield Ok of the default,
+    /// or Err(SequenceError::Empty) when the default is None.
+    fn first_with<P: FnMut(&Self::Item) -> bool>(self, predicate: P, default: Option<Self::Item>) -> FirstWith<Self, P>
+        where Self: Sized
+    {
+        FirstWith::new(self, predicate, default)
+    }
+
+    /// Yield Ok of the value at index and end, or Err(SequenceError::OutOfRange).
+    fn element_at(self, index: usize) -> ElementAt<Self>
+        where Self: Sized
+    {
+        ElementAt::new(self, index, SequenceError::OutOfRange)
+    }
+
+    /// Yield Ok of the last value once the stream ends, or Err(SequenceError::Empty).
+    fn last(self) -> Last<Self>
+        where Self: Sized
+    {
+        Last::new(self)
+    }
+
+    /// Like last, but only over values matching the predicate, with the
+    /// default handled as in first_with.
+    fn last_with<P: FnMut(&Self::Item) -> bool>(self, predicate: P, default: Option<Self::Item>) -> LastWith<Self, P>
+        where Self: Sized
+    {
+        LastWith::new(self, predicate, default)
+    }
+
+    /// Yield Ok of the only value once the stream ends, or an Err if there
+    /// is none or more than one, see SequenceError.
+    fn single(self) -> Single<Self>
+        where Self: Sized
+    {
+        Single::new(self)
+    }
+
+    /// Yield the values until the notifier yields, then end.
+    /// Unlike StreamExt::take_until, which takes a future, the notifier is a
+    /// stream of any item type. With both traits in scope, call it as
+    /// RxStreamEx::take_until(s, notifier).
+    fn take_until<N: Stream>(self, notifier: N) -> TakeUntil<Self, N>
+        where Self: Sized
+    {
+        TakeUntil::new(self, notifier)
+    }
+
+    /// Drop the values until the notifier yields.
+    fn skip_until<N: Stream>(self, notifier: N) -> SkipUntil<Self, N>
+        where Self: Sized
+    {
+        SkipUntil::new(self, notifier)
+    }
+
+    /// Yield the last count values once the stream ends.
+    fn take_last(self, count: usize) -> TakeLast<Self>
+        where Self: Sized
+    {
+        TakeLast::new(self, count)
+    }
+
+    /// Drop the last count values. A value is yielded once count more came.
+    fn skip_last(self, count: usize) -> SkipLast<Self>
+        where Self: Sized
+    {
+        SkipLast::new(self, count)
+    }
+
+    /// Drop every value, only the end is kept.
+    fn ignore_elements(self) -> IgnoreElements<Self>
+        where Self: Sized
+    {
+        IgnoreElements::new(self)
+    }
+}
+
+impl<T> RxTryStreamEx for T where T: TryStream {}
+
+/// Operators over fallible streams, following the error notification of rxjs.
+/// The sources are streams of Result sharing the same error type. The first
+/// Err of any source is yielded and ends the result stream right away, and
+/// the sources are not polled anymore. Otherwise they behave the same as
+/// their counterparts in RxStreamEx, with the Ok values wrapped in Ok.
+pub trait RxTryStreamEx: TryStream {
+    fn try_combine_latest<S2>(self, other: S2) -> TryCombineLatest<Self, S2>
+        where Self: Sized, S2: TryStream<Error = Self::Error>, Self::Ok: Clone, S2::Ok: Clone
+    {
+        ShortCircuit::new(|slot| CombineLatest::new(slot.take_oks(self), slot.take_oks(other)))
+    }
+
+    fn try_fork_join<S2>(self, other: S2) -> TryForkJoin<Self, S2>
+        where Self: Sized, S2: TryStream<Error = Self::Error>
+    {
+        ShortCircuit::new(|slot| ForkJoin::new(slot.take_oks(self), slot.take_oks(other)))
+    }
+
+    fn try_with_latest_from<S2>(self, other: S2) -> TryWithLatestFrom<Self, S2>
+        where Self: Sized, S2: TryStream<Error = Self::Error>, S2::Ok: Clone
+    {
+        ShortCircuit::new(|slot| WithLatestFrom::new(slot.take_oks(self), slot.take_oks(other)))
+    }
+
+    /// An error counts as a response, so a stream failing first wins the race
+    /// and its error is yielded.
+    fn try_race<S2>(self, other: S2) -> TryRace<Self, S2>
+        where Self: Sized, S2: TryStream<Ok = Self::Ok, Error = Self::Error>
+    {
+        ShortCircuit::new(|slot| Race::new(slot.take_oks(self), slot.take_oks(other)))
+    }
+
+    fn try_pairwise(self) -> TryPairwise<Self>
+        where Self: Sized, Self::Ok: Clone
+    {
+        ShortCircuit::new(|slot| Pairwise::new(slot.take_oks(self)))
+    }
+
+    /// On error, the values buffered so far are dropped, as in rxjs.
+    fn try_buffer_count(self, count: usize) -> TrySimpleCountBufferedStream<Self>
+        where Self: Sized
+    {
+        ShortCircuit::new(|slot| SimpleCountBufferedStream::new(slot.take_oks(self), count))
+    }
+
+    fn try_buffer_count_with_skip(self, count: usize, skip: usize) -> TryOverlappedCountBufferedStream<Self>
+        where Self: Sized, Self::Ok: Clone
+    {
+        ShortCircuit::new(|slot| OverlappedCountBufferedStream::new(slot.take_oks(self), count, skip))
+    }
+
+    fn try_buffer_time(self, time_span: u64) -> TrySimpleTimeBufferredStream<Self>
+        where Self: Sized
+    {
+        ShortCircuit::new(|slot| SimpleTimeBufferredStream::new(slot.take_oks(self), time_span))
+    }
+
+    fn try_buffer_time_with_scheduler<Sch: Scheduler>(self, time_span: u64, scheduler: Sch) -> TrySimpleTimeBufferredStream<Self, Sch>
+        where Self: Sized
+    {
+        ShortCircuit::new(|slot| SimpleTimeBufferredStream::new_with_scheduler(slot.take_oks(self), time_span, scheduler))
+    }
+
+    fn try_buffer_time_with_external_timer<Sch: Scheduler>(
+        self,
+        timer_stream: Pin<&mut source::TimerStream<Sch>>,
+    ) -> TrySimpleExternalTimeBufferredStream<'_, Self, Sch>
+        where Self: Sized
+    {
+        ShortCircuit::new(|slot| SimpleExternalTimeBufferredStream::new_with_timer_stream(slot.take_oks(self), timer_stream))
+    }
+
+    fn try_buffer_time_with_creation_interval(
+        self,
+        time_span: u64,
+        creation_interval: u64,
+    ) -> TryOverlappedTimeBufferedStream<Self>
+        where Self: Sized, Self::Ok: Clone
+    {
+        ShortCircuit::new(|slot| OverlappedTimeBufferedStream::new(slot.take_oks(self), time_span, creation_interval))
+    }
+
+    fn try_buffer_time_with_creation_interval_and_scheduler<Sch: Scheduler>(
+        self,
+        time_span: u64,
+        creation_interval: u64,
+        scheduler: Sch,
+    ) -> TryOverlappedTimeBufferedStream<Self, Sch>
+        where Self: Sized, Self::Ok: Clone
+    {
+        ShortCircuit::new(|slot| OverlappedTimeBufferedStream::new_with_scheduler(slot.take_oks(self), time_span, creation_interval, scheduler))
+    }
+
+    /// On the first error, switch to the replacement stream made from it.
+    /// Errors of the replacement stream are yielded as they are.
+    fn catch_error<F, S2>(self, f: F) -> CatchError<Self, F, S2>
+        where Self: Sized, F: FnOnce(Self::Error) -> S2, S2: TryStream<Ok = Self::Ok>
+    {
+        CatchError::new(self, f)
+    }
+
+    /// See on_error_resume_next, with this stream going first.
+    fn on_error_resume_next(self, others: Vec<Self>) -> OnErrorResumeNext<Self>
+        where Self: Sized
+    {
+        on_error_resume_next(std::iter::once(self).chain(others).collect())
+    }
+
+    /// After an error, re-create the stream from the factory, up to count
+    /// times in total. The error is yielded once no retry is left.
+    /// Values yielded before an error are kept, as in rxjs.
+    fn retry<F: FnMut() -> Self>(self, count: usize, factory: F) -> Retry<Self, F, RetryCount>
+        where Self: Sized
+    {
+        Retry::new(self, factory, RetryCount::new(count))
+    }
+
+    /// Same as retry, but wait before re-creating the stream, see Backoff.
+    fn retry_with_backoff<F: FnMut() -> Self>(self, backoff: Backoff, factory: F) -> Retry<Self, F, BackoffNotifier<DefaultScheduler>>
+        where Self: Sized
+    {
+        self.retry_with_backoff_and_scheduler(backoff, factory, DefaultScheduler::default())
+    }
+
+    fn retry_with_backoff_and_scheduler<F: FnMut() -> Self, Sch: Scheduler>(
+        self,
+        backoff: Backoff,
+        factory: F,
+        scheduler: Sch,
+    ) -> Retry<Self, F, BackoffNotifier<Sch>>
+        where Self: Sized
+    {
+        Retry::new(self, factory, BackoffNotifier::new(backoff, scheduler))
+    }
+
+    /// After an error, the notifier is called with it, and the stream is
+    /// re-created from the factory once the returned future resolves to Ok.
+    /// If it resolves to Err, that error is yielded and the stream ends.
+    fn retry_when<F: FnMut() -> Self, N: RetryNotifier<Self::Error>>(self, factory: F, notifier: N) -> Retry<Self, F, N>
+        where Self: Sized
+    {
+        Retry::new(self, factory, notifier)
+    }
+}
@@ -0,0 +1,2 @@
+pub mod share;
+pub mod partition;
@@ -0,0 +1,115 @@
+use futures::task::{Context, Poll};
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use futures::Stream;
+use super::share::Waiters;
+
+// Upstream values a half polls for the other one before it yields the task
+const ROUNDS: usize = 32;
+
+struct Halves<S: Stream, P> {
+    // None once the upstream ended
+    upstream: Option<Pin<Box<S>>>,
+    predicate: P,
+    // Values waiting for the matching and the non-matching half
+    queues: [VecDeque<S::Item>; 2],
+    dropped: [bool; 2],
+    waiters: Arc<Waiters>,
+}
+
+/// One half of partition. The upstream is polled by whichever half needs
+/// the next value, and the values of the other half are queued until it
+/// takes them, so the halves can be polled from different tasks at their
+/// own pace. A dropped half drops its values.
+#[must_use = "streams do nothing unless polled"]
+pub struct Partition<S: Stream, P> {
+    halves: Arc<Mutex<Halves<S, P>>>,
+    // 0 for the matching half, 1 for the other one
+    side: usize,
+}
+
+impl<S, P> Partition<S, P>
+    where S: Stream, P: FnMut(&S::Item) -> bool
+{
+    pub fn new(s: S, predicate: P) -> (Self, Self) {
+        let halves = Arc::new(Mutex::new(Halves {
+            upstream: Some(Box::pin(s)),
+            predicate,
+            queues: [VecDeque::new(), VecDeque::new()],
+            dropped: [false, false],
+            waiters: Arc::default(),
+        }));
+        (Partition { halves: halves.clone(), side: 0 }, Partition { halves, side: 1 })
+    }
+}
+
+impl<S, P> Stream for Partition<S, P>
+    where S: Stream, P: FnMut(&S::Item) -> bool
+{
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let side = self.side;
+        let mut halves = self.halves.lock().unwrap();
+        let halves = &mut *halves;
+        let waker = futures::task::waker(halves.waiters.clone());
+        let mut queued = false;
+        for _ in 0..ROUNDS {
+            if let Some(v) = halves.queues[side].pop_front() {
+                if queued {
+                    waker.wake_by_ref();
+                }
+                return Poll::Ready(Some(v))
+            }
+            let upstream = match halves.upstream.as_mut() {
+                Some(upstream) => upstream,
+                None => return Poll::Ready(None),
+            };
+            // Registered on every round, as the upstream may have woken the
+            // waiters while it was polled
+            halves.waiters.register(cx.waker());
+            match upstream.as_mut().poll_next(&mut Context::from_waker(&waker)) {
+                Poll::Ready(Some(v)) => {
+                    let to = if (halves.predicate)(&v) { 0 } else { 1 };
+                    if to == side {
+                        if queued {
+                            waker.wake_by_ref();
+                        }
+                        return Poll::Ready(Some(v))
+                    }
+                    if !halves.dropped[to] {
+                        halves.queues[to].push_back(v);
+                        queued = true;
+                    }
+                }
+                Poll::Ready(None) => {
+                    halves.upstream = None;
+                    waker.wake_by_ref();
+                }
+                Poll::Pending => {
+                    if queued {
+                        waker.wake_by_ref();
+                    }
+                    return Poll::Pending
+                }
+            }
+        }
+        // Only values for the other half so far, let it take them and come
+        // back for more rather than hold the task
+        waker.wake_by_ref();
+        cx.waker().wake_by_ref();
+        Poll::Pending
+    }
+}
+
+impl<S: Stream, P> Drop for Partition<S, P> {
+    fn drop(&mut self) {
+        let mut halves = self.halves.lock().unwrap();
+        halves.dropped[self.side] = true;
+        halves.queues[self.side].clear();
+    }
+}
@@ -0,0 +1,236 @@
+use futures::task::{ArcWake, Context, Poll, Waker};
+use std::collections::VecDeque;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, Weak};
+use futures::{Stream, StreamExt};
+use super::super::super::subject::{Core, Kind, Subscription};
+
+/// Wake every handle waiting on the upstream, as any of them may poll it next.
+#[derive(Debug, Default)]
+pub(crate) struct Waiters {
+    wakers: Mutex<Vec<Waker>>,
+}
+
+impl Waiters {
+    pub(crate) fn register(&self, waker: &Waker) {
+        let mut wakers = self.wakers.lock().unwrap();
+        if !wakers.iter().any(|w| w.will_wake(waker)) {
+            wakers.push(waker.clone());
+        }
+    }
+}
+
+impl ArcWake for Waiters {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        let wakers = std::mem::take(&mut *arc_self.wakers.lock().unwrap());
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Multicast<S: Stream> {
+    core: Arc<Mutex<Core<S::Item>>>,
+    // None once the upstream ended
+    upstream: Mutex<Option<Pin<Box<S>>>>,
+    connected: AtomicBool,
+    waiters: Arc<Waiters>,
+}
+
+/// One subscription to a multicast upstream. The upstream is polled by
+/// whichever handle needs the next value, and every value it yields is
+/// queued for all handles, so the handles see the same values in lock-step
+/// without the upstream running on its own task. Cloning a handle subscribes
+/// again, and the upstream is dropped with the last handle.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Shared<S: Stream> {
+    multicast: Arc<Multicast<S>>,
+    subscription: Subscription<S::Item>,
+}
+
+impl<S: Stream> Multicast<S> where S::Item: Clone {
+    fn new(s: Option<S>, kind: Kind, connected: bool) -> Arc<Self> {
+        let core = Core::new(kind, VecDeque::new());
+        if s.is_none() {
+            // Nothing to poll, the handles end right away
+            core.lock().unwrap().complete(None);
+        }
+        Arc::new(Multicast {
+            core,
+            upstream: Mutex::new(s.map(Box::pin)),
+            connected: AtomicBool::new(connected),
+            waiters: Arc::default(),
+        })
+    }
+
+    fn connect(&self) {
+        self.connected.store(true, Ordering::Release);
+        futures::task::waker(self.waiters.clone()).wake();
+    }
+}
+
+impl<S: Stream> Shared<S> where S::Item: Clone {
+    fn new(s: S, kind: Kind, connected: bool) -> Self {
+        Shared::subscribe(Multicast::new(Some(s), kind, connected))
+    }
+
+    fn subscribe(multicast: Arc<Multicast<S>>) -> Self {
+        Shared {
+            subscription: Core::subscribe(&multicast.core),
+            multicast,
+        }
+    }
+
+    /// Late handles get only the values yielded after they subscribe.
+    pub fn share(s: S) -> Self {
+        Shared::new(s, Kind::Plain, true)
+    }
+
+    /// Late handles get up to buffer_size last values first.
+    pub fn share_replay(s: S, buffer_size: usize) -> Self {
+        Shared::new(s, Kind::Replay(buffer_size), true)
+    }
+}
+
+impl<S: Stream> Clone for Shared<S> where S::Item: Clone {
+    fn clone(&self) -> Self {
+        Shared::subscribe(self.multicast.clone())
+    }
+}
+
+impl<S: Stream> Stream for Shared<S> where S::Item: Clone {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let multicast = &this.multicast;
+        loop {
+            if let Poll::Ready(item) = this.subscription.poll_next_unpin(cx) {
+                return Poll::Ready(item)
+            }
+            multicast.waiters.register(cx.waker());
+            if !multicast.connected.load(Ordering::Acquire) {
+                return Poll::Pending
+            }
+            // Another handle is polling the upstream, and it either queues
+            // a value for this one or registers the waiters
+            let mut upstream = match multicast.upstream.try_lock() {
+                Ok(upstream) => upstream,
+                Err(_) => return Poll::Pending,
+            };
+            let s = match upstream.as_mut() {
+                Some(s) => s,
+                None => return Poll::Pending,
+            };
+            let waker = futures::task::waker(multicast.waiters.clone());
+            match s.as_mut().poll_next(&mut Context::from_waker(&waker)) {
+                Poll::Ready(Some(item)) => multicast.core.lock().unwrap().next(item),
+                Poll::Ready(None) => {
+                    *upstream = None;
+                    multicast.core.lock().unwrap().complete(None);
+                }
+                Poll::Pending => return Poll::Pending,
+            }
+        }
+    }
+}
+
+/// A multicast upstream which is not polled until connect, see publish.
+#[must_use = "streams do nothing unless polled"]
+pub struct Published<S: Stream> {
+    multicast: Arc<Multicast<S>>,
+}
+
+// Derived Debug would not require the items to be Debug
+impl<S: Stream + fmt::Debug> fmt::Debug for Published<S> where S::Item: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Published").field("multicast", &self.multicast).finish()
+    }
+}
+
+impl<S: Stream> Published<S> where S::Item: Clone {
+    pub fn new(s: S) -> Self {
+        Published {
+            multicast: Multicast::new(Some(s), Kind::Plain, false),
+        }
+    }
+
+    /// New handle, which gets the values yielded after it subscribes.
+    pub fn subscribe(&self) -> Shared<S> {
+        Shared::subscribe(self.multicast.clone())
+    }
+
+    /// Start polling the upstream once any handle is polled.
+    pub fn connect(&self) {
+        self.multicast.connect()
+    }
+
+    /// Connect on the first subscription and disconnect with the last
+    /// handle, see RefCount. The handles subscribed on this Published count
+    /// as well.
+    pub fn ref_count(self) -> RefCount<S> {
+        RefCount {
+            published: Mutex::new(Some(self.multicast)),
+            current: Mutex::new(Weak::new()),
+            factory: None,
+        }
+    }
+}
+
+/// Handles of an upstream connected while any of them is alive, as the
+/// refCount of rxjs. The first subscribe connects, and the upstream is
+/// dropped with the last handle. A later subscribe connects again on the
+/// stream made by the factory, or ends right away for a ref counted
+/// Published, as its stream cannot be polled from the start again.
+#[must_use = "streams do nothing unless polled"]
+pub struct RefCount<S: Stream, F = fn() -> S> {
+    // The Published waiting for the first subscription
+    published: Mutex<Option<Arc<Multicast<S>>>>,
+    current: Mutex<Weak<Multicast<S>>>,
+    factory: Option<Mutex<F>>,
+}
+
+impl<S: Stream + fmt::Debug, F> fmt::Debug for RefCount<S, F> where S::Item: fmt::Debug {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RefCount")
+            .field("published", &self.published)
+            .field("current", &self.current)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S: Stream, F: FnMut() -> S> RefCount<S, F> where S::Item: Clone {
+    /// Subscribe to a new stream made by the factory on every connection.
+    pub fn new(factory: F) -> Self {
+        RefCount {
+            published: Mutex::new(None),
+            current: Mutex::new(Weak::new()),
+            factory: Some(Mutex::new(factory)),
+        }
+    }
+
+    pub fn subscribe(&self) -> Shared<S> {
+        let mut current = self.current.lock().unwrap();
+        if let Some(multicast) = current.upgrade() {
+            return Shared::subscribe(multicast)
+        }
+        let multicast = match self.published.lock().unwrap().take() {
+            Some(multicast) => multicast,
+            None => {
+                let s = self.factory.as_ref().map(|factory| (factory.lock().unwrap())());
+                Multicast::new(s, Kind::Plain, false)
+            }
+        };
+        *current = Arc::downgrade(&multicast);
+        let handle = Shared::subscribe(multicast);
+        handle.multicast.connect();
+        handle
+    }
+}
@@ -13,7 +13,7 @@ pub trait Buffer {
     /// so the buffer can decide whether to return the partially buffered item.
     /// By default it calls to poll_buffer
     fn poll_buffer_after_done(&mut self, cx: &mut Context) -> Option<Vec<Self::V>> {
-        return self.poll_buffer(cx);
+        self.poll_buffer(cx)
     }
 }
 
@@ -40,7 +40,7 @@ impl<S, B> Stream for BufferedStream<S,B> where S: Stream, B: Buffer<V=S::Item>
             if let Some(r) = buffer.poll_buffer_after_done(cx) {
                 return Poll::Ready(Some(r))
             } 
-            return Poll::Ready(None)
+            Poll::Ready(None)
         } else {
             // Before stream is end, buffer will be polled with
             // poll and None means not ready.
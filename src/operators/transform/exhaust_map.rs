@@ -0,0 +1,62 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::pin::Pin;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use pin_project::pin_project;
+
+#[pin_project(project=ExhaustMapProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ExhaustMap<S: Stream, F, U> {
+    #[pin]
+    s: Fuse<S>,
+    f: F,
+    #[pin]
+    inner: Option<U>,
+}
+
+impl<S, F, U> ExhaustMap<S, F, U> 
+    where S: Stream, F: FnMut(S::Item) -> U, U: Stream
+{
+    pub fn new(s: S, f: F) -> Self {
+        ExhaustMap {
+            s: s.fuse(),
+            f,
+            inner: None,
+        }
+    }
+}
+
+impl<S, F, U> Stream for ExhaustMap<S, F, U> 
+    where S: Stream, F: FnMut(S::Item) -> U, U: Stream
+{
+    type Item = U::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>, 
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let ExhaustMapProj { mut s, f, mut inner } = self.project();
+        loop {
+            // Finish the running inner stream first, so the outer values
+            // arriving meanwhile are known to be ignored
+            if let Some(current) = inner.as_mut().as_pin_mut() {
+                match current.poll_next(cx) {
+                    Poll::Ready(None) => inner.set(None),
+                    x @ Poll::Ready(Some(_)) => return x,
+                    Poll::Pending => (),
+                }
+            }
+            match s.as_mut().poll_next(cx) {
+                Poll::Ready(Some(v)) => {
+                    if inner.is_none() {
+                        inner.set(Some(f(v)));
+                    }
+                }
+                Poll::Ready(None) if inner.is_none() => return Poll::Ready(None),
+                _ => return Poll::Pending,
+            }
+        }
+    }
+}
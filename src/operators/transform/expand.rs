@@ -0,0 +1,75 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use futures::{Stream, StreamExt};
+use futures::stream::{Fuse, SelectAll};
+use pin_project::pin_project;
+
+/// Yield every value, and map it to an inner stream whose values are
+/// handled the same way, recursively. At most concurrency inner streams run
+/// at a time, the values waiting to be mapped are queued.
+#[pin_project(project=ExpandProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Expand<S: Stream, F, U> {
+    #[pin]
+    s: Fuse<S>,
+    f: F,
+    inners: SelectAll<Pin<Box<U>>>,
+    queued: VecDeque<S::Item>,
+    concurrency: usize,
+}
+
+impl<S, F, U> Expand<S, F, U>
+    where S: Stream, S::Item: Clone, F: FnMut(S::Item) -> U, U: Stream<Item = S::Item>
+{
+    pub fn new(s: S, f: F, concurrency: usize) -> Self {
+        assert!(concurrency > 0, "expand concurrency must be non-zero");
+        Expand {
+            s: s.fuse(),
+            f,
+            inners: SelectAll::new(),
+            queued: VecDeque::new(),
+            concurrency,
+        }
+    }
+}
+
+impl<S, F, U> Stream for Expand<S, F, U>
+    where S: Stream, S::Item: Clone, F: FnMut(S::Item) -> U, U: Stream<Item = S::Item>
+{
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let ExpandProj { mut s, f, inners, queued, concurrency } = self.project();
+        loop {
+            while inners.len() < *concurrency {
+                match queued.pop_front() {
+                    Some(v) => inners.push(Box::pin(f(v))),
+                    None => break,
+                }
+            }
+            let running = inners.len();
+            if let Poll::Ready(Some(v)) = inners.poll_next_unpin(cx) {
+                queued.push_back(v.clone());
+                return Poll::Ready(Some(v))
+            }
+            // Some inner streams ended, which makes room for the queued values
+            if inners.len() < running && !queued.is_empty() {
+                continue
+            }
+            if let Poll::Ready(Some(v)) = s.as_mut().poll_next(cx) {
+                queued.push_back(v.clone());
+                return Poll::Ready(Some(v))
+            }
+            if s.is_done() && inners.is_empty() && queued.is_empty() {
+                return Poll::Ready(None)
+            }
+            return Poll::Pending
+        }
+    }
+}
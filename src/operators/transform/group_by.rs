@@ -0,0 +1,283 @@
+use futures::task::{Context, Poll};
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use futures::Stream;
+use futures::stream::Pending;
+use super::super::filtering::duration::DurationSelector;
+use super::super::multicasting::share::Waiters;
+
+#[derive(Debug)]
+struct Slot<V, U> {
+    // At most one value, the upstream is not polled while a group holds one
+    items: VecDeque<V>,
+    open: bool,
+    duration: Option<Pin<Box<U>>>,
+}
+
+/// What the outer stream and the groups share, the upstream is polled by
+/// whichever of them needs the next value.
+struct State<S: Stream, K, V, F, E, D: DurationSelector<K>> {
+    // None once the upstream ended
+    upstream: Option<Pin<Box<S>>>,
+    key_selector: F,
+    element_selector: E,
+    duration_selector: D,
+    keys: HashMap<K, usize>,
+    slots: HashMap<usize, Slot<V, D::Duration>>,
+    // Groups not yielded by the outer stream yet, None once it is dropped
+    opened: Option<VecDeque<(K, usize)>>,
+    next_id: usize,
+    waiters: Arc<Waiters>,
+}
+
+impl<S: Stream, K, V, F, E, D: DurationSelector<K>> State<S, K, V, F, E, D> {
+    fn wake(&self) {
+        futures::task::waker(self.waiters.clone()).wake();
+    }
+
+    fn drop_group(&mut self, id: usize) {
+        if let Some(slot) = self.slots.remove(&id) {
+            self.keys.retain(|_, i| *i != id);
+            if !slot.items.is_empty() {
+                self.wake();
+            }
+        }
+    }
+
+    fn drop_outer(&mut self) {
+        for (_, id) in self.opened.take().into_iter().flatten() {
+            self.drop_group(id);
+        }
+    }
+}
+
+impl<S, K, V, F, E, D> State<S, K, V, F, E, D>
+    where S: Stream,
+          K: Eq + Hash + Clone,
+          F: FnMut(&S::Item) -> K,
+          E: FnMut(S::Item) -> V,
+          D: DurationSelector<K>,
+{
+
+    /// Ready once a group got a value or was closed, Pending when nothing
+    /// can change before a wakeup.
+    fn drive(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        self.waiters.register(cx.waker());
+        let waker = futures::task::waker(self.waiters.clone());
+        let mut cx = Context::from_waker(&waker);
+        let mut closed = false;
+        for slot in self.slots.values_mut() {
+            if let Some(duration) = slot.duration.as_mut() {
+                if duration.as_mut().poll_next(&mut cx).is_ready() {
+                    slot.open = false;
+                    slot.duration = None;
+                    closed = true;
+                }
+            }
+        }
+        if closed {
+            // The next value of a closed key opens a new group
+            let slots = &self.slots;
+            self.keys.retain(|_, id| slots[id].open);
+            self.wake();
+            return Poll::Ready(())
+        }
+        if self.slots.values().any(|slot| !slot.items.is_empty()) {
+            return Poll::Pending
+        }
+        let upstream = match self.upstream.as_mut() {
+            Some(upstream) => upstream,
+            None => return Poll::Pending,
+        };
+        match upstream.as_mut().poll_next(&mut cx) {
+            Poll::Ready(Some(item)) => self.push(item),
+            Poll::Ready(None) => {
+                self.upstream = None;
+                self.keys.clear();
+                for slot in self.slots.values_mut() {
+                    slot.open = false;
+                    slot.duration = None;
+                }
+                self.wake();
+            }
+            Poll::Pending => return Poll::Pending,
+        }
+        Poll::Ready(())
+    }
+
+    fn push(&mut self, item: S::Item) {
+        let key = (self.key_selector)(&item);
+        let id = match self.keys.get(&key) {
+            Some(id) => *id,
+            None => {
+                // Nobody can get a new group once the outer stream is dropped
+                let opened = match self.opened.as_mut() {
+                    Some(opened) => opened,
+                    None => return,
+                };
+                let id = self.next_id;
+                self.next_id += 1;
+                opened.push_back((key.clone(), id));
+                self.keys.insert(key.clone(), id);
+                self.slots.insert(id, Slot {
+                    items: VecDeque::new(),
+                    open: true,
+                    duration: None,
+                });
+                id
+            }
+        };
+        let v = (self.element_selector)(item);
+        let duration = self.duration_selector.select(&key);
+        let slot = self.slots.get_mut(&id).unwrap();
+        slot.items.push_back(v);
+        slot.duration = Some(Box::pin(duration));
+        self.wake();
+    }
+
+    fn poll_opened(&mut self, cx: &mut Context<'_>) -> Poll<Option<(K, usize)>> {
+        loop {
+            if let Some(group) = self.opened.as_mut().and_then(|opened| opened.pop_front()) {
+                return Poll::Ready(Some(group))
+            }
+            if self.upstream.is_none() {
+                return Poll::Ready(None)
+            }
+            ready!(self.drive(cx));
+        }
+    }
+
+    fn poll_group(&mut self, id: usize, cx: &mut Context<'_>) -> Poll<Option<V>> {
+        loop {
+            let slot = match self.slots.get_mut(&id) {
+                Some(slot) => slot,
+                None => return Poll::Ready(None),
+            };
+            if let Some(v) = slot.items.pop_front() {
+                // The upstream may be polled again
+                self.wake();
+                return Poll::Ready(Some(v))
+            }
+            if !slot.open {
+                self.slots.remove(&id);
+                return Poll::Ready(None)
+            }
+            ready!(self.drive(cx));
+        }
+    }
+}
+
+type SharedState<S, K, V, F, E, D> = Arc<Mutex<State<S, K, V, F, E, D>>>;
+
+/// Element selector of the group_by variants keeping the values as they are.
+pub type SameElement<T> = fn(T) -> T;
+
+/// Duration selector of the group_by variants whose groups stay open until
+/// the upstream ends.
+pub type NoDuration<K> = fn(&K) -> Pending<()>;
+
+/// GroupBy of the plain group_by, see RxStreamEx::group_by.
+pub type SimpleGroupBy<S, K, F> = GroupBy<S, K, <S as Stream>::Item, F, SameElement<<S as Stream>::Item>, NoDuration<K>>;
+
+/// Stream of the groups of values sharing a key. The upstream is polled by
+/// whichever of the outer stream and the groups needs the next value, and it
+/// is not polled while a group holds a value it has not yielded yet, so the
+/// groups must be polled concurrently with the outer stream, as merge_map
+/// does. A dropped group drops its values, and the next value of its key
+/// opens a new group, as does the next value of a key whose group closed.
+#[must_use = "streams do nothing unless polled"]
+pub struct GroupBy<S: Stream, K, V, F, E, D: DurationSelector<K>> {
+    groups: SharedState<S, K, V, F, E, D>,
+}
+
+impl<S, K, V, F, E, D> GroupBy<S, K, V, F, E, D>
+    where S: Stream,
+          K: Eq + Hash + Clone,
+          F: FnMut(&S::Item) -> K,
+          E: FnMut(S::Item) -> V,
+          D: DurationSelector<K>,
+{
+    pub fn new(s: S, key_selector: F, element_selector: E, duration_selector: D) -> Self {
+        GroupBy {
+            groups: Arc::new(Mutex::new(State {
+                upstream: Some(Box::pin(s)),
+                key_selector,
+                element_selector,
+                duration_selector,
+                keys: HashMap::new(),
+                slots: HashMap::new(),
+                opened: Some(VecDeque::new()),
+                next_id: 0,
+                waiters: Arc::default(),
+            })),
+        }
+    }
+}
+
+impl<S, K, V, F, E, D> Stream for GroupBy<S, K, V, F, E, D>
+    where S: Stream,
+          K: Eq + Hash + Clone,
+          F: FnMut(&S::Item) -> K,
+          E: FnMut(S::Item) -> V,
+          D: DurationSelector<K>,
+{
+    type Item = GroupedStream<S, K, V, F, E, D>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let opened = self.groups.lock().unwrap().poll_opened(cx);
+        opened.map(|group| group.map(|(key, id)| GroupedStream {
+            key,
+            id,
+            groups: self.groups.clone(),
+        }))
+    }
+}
+
+impl<S: Stream, K, V, F, E, D: DurationSelector<K>> Drop for GroupBy<S, K, V, F, E, D> {
+    fn drop(&mut self) {
+        self.groups.lock().unwrap().drop_outer();
+    }
+}
+
+/// Values of one key, see GroupBy. It ends when the upstream ends or the
+/// duration of the group yields or ends.
+#[must_use = "streams do nothing unless polled"]
+pub struct GroupedStream<S: Stream, K, V, F, E, D: DurationSelector<K>> {
+    key: K,
+    id: usize,
+    groups: SharedState<S, K, V, F, E, D>,
+}
+
+impl<S: Stream, K, V, F, E, D: DurationSelector<K>> GroupedStream<S, K, V, F, E, D> {
+    pub fn key(&self) -> &K {
+        &self.key
+    }
+}
+
+impl<S, K, V, F, E, D> Stream for GroupedStream<S, K, V, F, E, D>
+    where S: Stream,
+          K: Eq + Hash + Clone,
+          F: FnMut(&S::Item) -> K,
+          E: FnMut(S::Item) -> V,
+          D: DurationSelector<K>,
+{
+    type Item = V;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.groups.lock().unwrap().poll_group(self.id, cx)
+    }
+}
+
+impl<S: Stream, K, V, F, E, D: DurationSelector<K>> Drop for GroupedStream<S, K, V, F, E, D> {
+    fn drop(&mut self) {
+        self.groups.lock().unwrap().drop_group(self.id);
+    }
+}
@@ -0,0 +1,66 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::pin::Pin;
+use futures::{Stream, StreamExt};
+use futures::stream::{Fuse, SelectAll};
+use pin_project::pin_project;
+
+#[pin_project(project=MergeMapProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct MergeMap<S: Stream, F, U> {
+    #[pin]
+    s: Fuse<S>,
+    f: F,
+    inners: SelectAll<Pin<Box<U>>>,
+    concurrency: usize,
+}
+
+/// concat_map is merge_map running one inner stream at a time.
+pub type ConcatMap<S, F, U> = MergeMap<S, F, U>;
+
+impl<S, F, U> MergeMap<S, F, U> 
+    where S: Stream, F: FnMut(S::Item) -> U, U: Stream
+{
+    pub fn new(s: S, f: F, concurrency: usize) -> Self {
+        assert!(concurrency > 0, "merge_map concurrency must be non-zero");
+        MergeMap {
+            s: s.fuse(),
+            f,
+            inners: SelectAll::new(),
+            concurrency,
+        }
+    }
+}
+
+impl<S, F, U> Stream for MergeMap<S, F, U> 
+    where S: Stream, F: FnMut(S::Item) -> U, U: Stream
+{
+    type Item = U::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>, 
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let MergeMapProj { mut s, f, inners, concurrency } = self.project();
+        loop {
+            // The outer stream is not polled while the limit is reached, so
+            // its values wait there rather than in a buffer
+            while inners.len() < *concurrency {
+                match s.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(v)) => inners.push(Box::pin(f(v))),
+                    _ => break,
+                }
+            }
+            let running = inners.len();
+            match inners.poll_next_unpin(cx) {
+                Poll::Ready(Some(item)) => return Poll::Ready(Some(item)),
+                // No inner stream is running
+                Poll::Ready(None) if s.is_done() => return Poll::Ready(None),
+                // Some inner streams ended, which makes room for the outer values
+                _ if inners.len() < running && !s.is_done() => (),
+                _ => return Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,68 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::pin::Pin;
+use futures::{Stream, StreamExt};
+use futures::stream::{Fuse, SelectAll};
+use pin_project::pin_project;
+
+/// merge_map whose function gets the accumulator along with the value. Each
+/// value of the inner streams is yielded and becomes the accumulator.
+#[pin_project(project=MergeScanProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct MergeScan<S: Stream, F, A, U> {
+    #[pin]
+    s: Fuse<S>,
+    f: F,
+    acc: A,
+    inners: SelectAll<Pin<Box<U>>>,
+    concurrency: usize,
+}
+
+impl<S, F, A, U> MergeScan<S, F, A, U>
+    where S: Stream, F: FnMut(A, S::Item) -> U, U: Stream<Item = A>, A: Clone
+{
+    pub fn new(s: S, seed: A, f: F, concurrency: usize) -> Self {
+        assert!(concurrency > 0, "merge_scan concurrency must be non-zero");
+        MergeScan {
+            s: s.fuse(),
+            f,
+            acc: seed,
+            inners: SelectAll::new(),
+            concurrency,
+        }
+    }
+}
+
+impl<S, F, A, U> Stream for MergeScan<S, F, A, U>
+    where S: Stream, F: FnMut(A, S::Item) -> U, U: Stream<Item = A>, A: Clone
+{
+    type Item = A;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let MergeScanProj { mut s, f, acc, inners, concurrency } = self.project();
+        loop {
+            // Same as merge_map, the outer values wait in the outer stream
+            // while the limit is reached
+            while inners.len() < *concurrency {
+                match s.as_mut().poll_next(cx) {
+                    Poll::Ready(Some(v)) => inners.push(Box::pin(f(acc.clone(), v))),
+                    _ => break,
+                }
+            }
+            let running = inners.len();
+            match inners.poll_next_unpin(cx) {
+                Poll::Ready(Some(a)) => {
+                    *acc = a.clone();
+                    return Poll::Ready(Some(a))
+                }
+                Poll::Ready(None) if s.is_done() => return Poll::Ready(None),
+                _ if inners.len() < running && !s.is_done() => (),
+                _ => return Poll::Pending,
+            }
+        }
+    }
+}
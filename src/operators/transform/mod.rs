@@ -4,4 +4,14 @@ pub mod simple_time_buffer;
 pub mod overlapped_buffer;
 pub mod overlapped_count_buffer;
 pub mod overlapped_time_buffer;
-mod buffered_stream;
\ No newline at end of file
+mod buffered_stream;
+pub mod switch_map;
+pub mod merge_map;
+pub mod exhaust_map;
+pub mod notifier_buffer;
+pub mod toggle_buffer;
+pub mod window;
+pub mod scan;
+pub mod merge_scan;
+pub mod expand;
+pub mod group_by;
@@ -0,0 +1,97 @@
+use futures::task::Context;
+use futures::task::Poll;
+use super::buffered_stream::{Buffer, BufferedStream};
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use std::pin::Pin;
+
+/// Buffer closed whenever the notifier yields. Unlike the time buffers, the
+/// values buffered when the stream ends are still yielded, as in rxjs.
+/// The end of the notifier closes nothing.
+#[derive(Debug)]
+pub struct NotifierBuffer<V, N> {
+    vec: Vec<V>,
+    notifier: Pin<Box<Fuse<N>>>,
+    done: bool,
+}
+
+impl<V, N: Stream> NotifierBuffer<V, N> {
+    pub fn new(notifier: N) -> Self {
+        NotifierBuffer {
+            vec: Vec::new(),
+            notifier: Box::pin(notifier.fuse()),
+            done: false,
+        }
+    }
+}
+
+impl<V, N: Stream> Buffer for NotifierBuffer<V, N> {
+    type V = V;
+    fn insert(&mut self, v: V) {
+        self.vec.push(v);
+    }
+    fn poll_buffer(&mut self, cx: &mut Context) -> Option<Vec<V>> {
+        match self.notifier.as_mut().poll_next(cx) {
+            Poll::Ready(Some(_)) => Some(std::mem::take(&mut self.vec)),
+            _ => None,
+        }
+    }
+    fn poll_buffer_after_done(&mut self, _cx: &mut Context) -> Option<Vec<V>> {
+        if self.done {
+            None
+        } else {
+            self.done = true;
+            Some(std::mem::take(&mut self.vec))
+        }
+    }
+}
+
+pub type NotifierBufferedStream<S, N> = BufferedStream<S, NotifierBuffer<<S as Stream>::Item, N>>;
+
+impl<S: Stream, N: Stream> NotifierBufferedStream<S, N> {
+    pub fn new(s: S, notifier: N) -> Self {
+        NotifierBufferedStream {
+            s: s.fuse(),
+            buffer: NotifierBuffer::new(notifier),
+        }
+    }
+}
+
+/// Buffer closed whenever the stream made by closing_selector yields, which
+/// opens the next buffer with a new closing stream right away.
+#[derive(Debug)]
+pub struct WhenBuffer<V, F, C> {
+    buffer: NotifierBuffer<V, C>,
+    closing_selector: F,
+}
+
+impl<V, F: FnMut() -> C, C: Stream> Buffer for WhenBuffer<V, F, C> {
+    type V = V;
+    fn insert(&mut self, v: V) {
+        self.buffer.insert(v)
+    }
+    fn poll_buffer(&mut self, cx: &mut Context) -> Option<Vec<V>> {
+        let r = self.buffer.poll_buffer(cx);
+        if r.is_some() {
+            self.buffer.notifier = Box::pin((self.closing_selector)().fuse());
+        }
+        r
+    }
+    fn poll_buffer_after_done(&mut self, cx: &mut Context) -> Option<Vec<V>> {
+        self.buffer.poll_buffer_after_done(cx)
+    }
+}
+
+pub type WhenBufferedStream<S, F, C> = BufferedStream<S, WhenBuffer<<S as Stream>::Item, F, C>>;
+
+impl<S: Stream, F: FnMut() -> C, C: Stream> WhenBufferedStream<S, F, C> {
+    pub fn new(s: S, mut closing_selector: F) -> Self {
+        WhenBufferedStream {
+            s: s.fuse(),
+            buffer: WhenBuffer {
+                buffer: NotifierBuffer::new(closing_selector()),
+                closing_selector,
+            },
+        }
+    }
+}
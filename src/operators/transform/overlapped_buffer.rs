@@ -3,55 +3,65 @@ use super::buffered_stream::Buffer;
 use std::collections::VecDeque;
 
 pub trait BufferOpener {
-    fn check_open(&mut self) -> bool;
+    /// What a buffer is opened with, which is handed to the creator.
+    type Opening;
+    /// Checked after every value is inserted to the open buffers.
+    fn check_open(&mut self) -> Option<Self::Opening>;
+    /// Checked whenever the buffers are polled, for the openers driven by
+    /// another stream rather than by the values.
+    fn poll_open(&mut self, _cx: &mut Context) -> Option<Self::Opening> {
+        None
+    }
 }
 
-pub trait BufferCreator<B> {
-    fn new_buffer(&mut self) -> B;
+pub trait BufferCreator<B, T> {
+    fn new_buffer(&mut self, opening: T) -> B;
 }
 
 #[derive(Default)]
-pub struct OverlappedBuffer<B, O: BufferOpener, C: BufferCreator<B>> where B: Buffer, B::V: Clone {
+pub struct OverlappedBuffer<B, O: BufferOpener, C: BufferCreator<B, O::Opening>> where B: Buffer, B::V: Clone {
     pub buffers: VecDeque<B>,
     pub opener: O,
     pub creator: C
 }
 
-impl<B: Buffer, O: BufferOpener, C: BufferCreator<B>> OverlappedBuffer<B, O, C> where B::V: Clone {
+impl<B: Buffer, O: BufferOpener, C: BufferCreator<B, O::Opening>> OverlappedBuffer<B, O, C> where B::V: Clone {
     pub fn new_internal(opener: O, creator: C) -> Self {
         OverlappedBuffer {
             buffers: VecDeque::new(),
-            opener: opener,
-            creator: creator,
+            opener,
+            creator,
         }
     }
 }
 
-impl<B: Buffer, O: BufferOpener, C: BufferCreator<B>> Buffer for OverlappedBuffer<B, O, C> where B::V: Clone {
+impl<B: Buffer, O: BufferOpener, C: BufferCreator<B, O::Opening>> Buffer for OverlappedBuffer<B, O, C> where B::V: Clone {
     type V = B::V;
 
-    fn insert(&mut self, v:Self::V) -> () {
+    fn insert(&mut self, v:Self::V) {
         for buffer in self.buffers.iter_mut() {
             buffer.insert(v.clone())
         }
-        if self.opener.check_open() {
-            self.buffers.push_back(self.creator.new_buffer())
+        if let Some(opening) = self.opener.check_open() {
+            self.buffers.push_back(self.creator.new_buffer(opening))
         }
     }
     fn poll_buffer(&mut self, cx: &mut Context) -> Option<Vec<Self::V>> {
-        let result = if let Some(front) = self.buffers.front_mut() {
-            if let Some(r) = front.poll_buffer(cx) {
-                Some(r)
-            } else {
-                None
+        // One buffer opened per call, the openings may always be ready
+        if let Some(opening) = self.opener.poll_open(cx) {
+            self.buffers.push_back(self.creator.new_buffer(opening));
+            // The opener hasn't registered the waker, poll it again
+            cx.waker().wake_by_ref();
+        }
+        // Buffers closed by other streams may close in any order, so each
+        // one is polled. The first one ready in opening order is yielded.
+        for i in 0..self.buffers.len() {
+            if let Some(r) = self.buffers[i].poll_buffer(cx) {
+                self.buffers.remove(i);
+                return Some(r)
             }
-        } else {
-            None
-        };
-        if result.is_some() {
-            self.buffers.pop_front();
         }
-        result
+        None
     }
 
     fn poll_buffer_after_done(&mut self, cx: &mut Context) -> Option<Vec<Self::V>> {
@@ -11,19 +11,35 @@ pub struct CountBufferOpener {
 pub struct CountBufferCreator {
     max_count: usize,
 }
+impl CountBufferOpener {
+    pub fn new(skip: usize) -> Self {
+        CountBufferOpener {
+            skip,
+            skip_count: 0,
+        }
+    }
+}
+impl CountBufferCreator {
+    pub fn new(max_count: usize) -> Self {
+        CountBufferCreator {
+            max_count,
+        }
+    }
+}
 impl BufferOpener for CountBufferOpener {
-    fn check_open(&mut self) -> bool {
+    type Opening = ();
+    fn check_open(&mut self) -> Option<()> {
         self.skip_count += 1;
         if self.skip_count == self.skip {
             self.skip_count = 0;
-            true
+            Some(())
         } else {
-            false
+            None
         }
     }
 }
-impl<V: Clone> BufferCreator<SimpleCountBuffer<V>> for CountBufferCreator {
-    fn new_buffer(&mut self) -> SimpleCountBuffer<V> {
+impl<V: Clone> BufferCreator<SimpleCountBuffer<V>, ()> for CountBufferCreator {
+    fn new_buffer(&mut self, _opening: ()) -> SimpleCountBuffer<V> {
         SimpleCountBuffer::new(self.max_count)
     }
 }
@@ -32,13 +48,8 @@ pub type OverlappedCountBuffer<V> = OverlappedBuffer<SimpleCountBuffer<V>, Count
 
 impl<V: Clone> OverlappedCountBuffer<V> {
     fn new(max_count: usize, skip: usize) -> Self {
-        let mut r = OverlappedCountBuffer::new_internal(CountBufferOpener {
-            skip: skip,
-            skip_count: 0,
-        }, CountBufferCreator {
-            max_count: max_count,
-        });
-        let b = <CountBufferCreator as BufferCreator<SimpleCountBuffer<V>>>::new_buffer(&mut r.creator);
+        let mut r = OverlappedCountBuffer::new_internal(CountBufferOpener::new(skip), CountBufferCreator::new(max_count));
+        let b = <CountBufferCreator as BufferCreator<SimpleCountBuffer<V>, ()>>::new_buffer(&mut r.creator, ());
         r.buffers.push_back(b);
         r
     } 
@@ -1,64 +1,82 @@
 use super::overlapped_buffer::{BufferCreator, BufferOpener, OverlappedBuffer};
 use super::buffered_stream::BufferedStream;
 use super::simple_time_buffer::{new_simple_time_buffer, SimpleTimeBuffer};
+use super::super::super::scheduler::{Scheduler, DefaultScheduler};
 use std::time::{Duration, Instant};
 use futures::{Stream, StreamExt};
 
 
-pub struct TimeBufferOpener {
+pub struct TimeBufferOpener<Sch: Scheduler> {
     period: u64,
     last_check: Instant,
+    scheduler: Sch,
 }
-pub struct TimeBufferCreator {
+pub struct TimeBufferCreator<Sch: Scheduler> {
     time_span: u64,
+    scheduler: Sch,
 }
 
-impl TimeBufferOpener {
-    fn new(period: u64) -> Self {
+impl<Sch: Scheduler> TimeBufferOpener<Sch> {
+    pub fn new(period: u64, scheduler: Sch) -> Self {
         Self {
-            period: period,
-            last_check: Instant::now()
+            period,
+            last_check: scheduler.now(),
+            scheduler,
         }
     }
 }
 
-impl BufferOpener for TimeBufferOpener {
-    fn check_open(&mut self) -> bool {
-        let now = Instant::now();
+impl<Sch: Scheduler> TimeBufferCreator<Sch> {
+    pub fn new(time_span: u64, scheduler: Sch) -> Self {
+        Self {
+            time_span,
+            scheduler,
+        }
+    }
+}
+
+impl<Sch: Scheduler> BufferOpener for TimeBufferOpener<Sch> {
+    type Opening = ();
+    fn check_open(&mut self) -> Option<()> {
+        let now = self.scheduler.now();
         if now >= self.last_check + Duration::from_millis(self.period) {
             self.last_check = now;
-            true
+            Some(())
         } else {
-            false
+            None
         }
     }
 }
 
-impl<V: Clone> BufferCreator<SimpleTimeBuffer<V>> for TimeBufferCreator {
-    fn new_buffer(&mut self) -> SimpleTimeBuffer<V> {
-        new_simple_time_buffer(self.time_span)
+impl<V: Clone, Sch: Scheduler> BufferCreator<SimpleTimeBuffer<V, Sch>, ()> for TimeBufferCreator<Sch> {
+    fn new_buffer(&mut self, _opening: ()) -> SimpleTimeBuffer<V, Sch> {
+        new_simple_time_buffer(self.time_span, self.scheduler.clone())
     }
 }
 
-pub type OverlappedTimeBuffer<V> = OverlappedBuffer<SimpleTimeBuffer<V>, TimeBufferOpener, TimeBufferCreator>;
+pub type OverlappedTimeBuffer<V, Sch = DefaultScheduler> = OverlappedBuffer<SimpleTimeBuffer<V, Sch>, TimeBufferOpener<Sch>, TimeBufferCreator<Sch>>;
 
-impl<V: Clone> OverlappedTimeBuffer<V> {
-    fn new(time_span: u64, creation_interval: u64) -> Self {
-        let mut r = OverlappedTimeBuffer::new_internal(TimeBufferOpener::new(creation_interval), TimeBufferCreator {
-            time_span: time_span,
-        });
-        let b = <TimeBufferCreator as BufferCreator<SimpleTimeBuffer<V>>>::new_buffer(&mut r.creator);
+impl<V: Clone, Sch: Scheduler> OverlappedTimeBuffer<V, Sch> {
+    fn new(time_span: u64, creation_interval: u64, scheduler: Sch) -> Self {
+        let mut r = OverlappedTimeBuffer::new_internal(TimeBufferOpener::new(creation_interval, scheduler.clone()), TimeBufferCreator::new(time_span, scheduler));
+        let b = <TimeBufferCreator<Sch> as BufferCreator<SimpleTimeBuffer<V, Sch>, ()>>::new_buffer(&mut r.creator, ());
         r.buffers.push_back(b);
         r
-    } 
+    }
 }
 
-pub type OverlappedTimeBufferedStream<S> = BufferedStream<S, OverlappedTimeBuffer<<S as Stream>::Item>>;
+pub type OverlappedTimeBufferedStream<S, Sch = DefaultScheduler> = BufferedStream<S, OverlappedTimeBuffer<<S as Stream>::Item, Sch>>;
 impl<S: Stream> OverlappedTimeBufferedStream<S> where S::Item: Clone {
     pub fn new(s: S, time_span: u64, creation_interval: u64) -> Self {
+        OverlappedTimeBufferedStream::new_with_scheduler(s, time_span, creation_interval, DefaultScheduler::default())
+    }
+}
+
+impl<S: Stream, Sch: Scheduler> OverlappedTimeBufferedStream<S, Sch> where S::Item: Clone {
+    pub fn new_with_scheduler(s: S, time_span: u64, creation_interval: u64, scheduler: Sch) -> Self {
         OverlappedTimeBufferedStream {
             s: s.fuse(),
-            buffer: OverlappedTimeBuffer::new(time_span, creation_interval),
+            buffer: OverlappedTimeBuffer::new(time_span, creation_interval, scheduler),
         }
     }
-}
\ No newline at end of file
+}
@@ -3,7 +3,6 @@ use futures::task::Context;
 use std::pin::Pin;
 use futures::{Stream, StreamExt};
 use futures::stream::Fuse;
-use std::mem;
 use pin_project::pin_project;
 
 #[pin_project(project=PairwiseProj)]
@@ -43,14 +42,14 @@ impl<S> Stream for Pairwise<S> where S: Stream, S::Item: Clone {
         match inner2 {
             Some(item) => {
                 let current = item.clone();
-                return Poll::Ready(Some(
+                Poll::Ready(Some(
                     (
-                        mem::replace(previous, Some(item)).unwrap(), 
+                        previous.replace(item).unwrap(), 
                         current
                     )
-                ));
+                ))
             },
-            None => return Poll::Ready(None)
+            None => Poll::Ready(None)
         }
     }
 }
\ No newline at end of file
@@ -0,0 +1,179 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::pin::Pin;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use pin_project::pin_project;
+
+/// Yield the accumulator after folding each value into it, starting from
+/// the seed.
+#[pin_project(project=ScanProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Scan<S, F, A> {
+    #[pin]
+    s: S,
+    f: F,
+    acc: Option<A>,
+}
+
+impl<S, F, A> Scan<S, F, A>
+    where S: Stream, F: FnMut(A, S::Item) -> A, A: Clone
+{
+    pub fn new(s: S, seed: A, f: F) -> Self {
+        Scan {
+            s,
+            f,
+            acc: Some(seed),
+        }
+    }
+}
+
+impl<S, F, A> Stream for Scan<S, F, A>
+    where S: Stream, F: FnMut(A, S::Item) -> A, A: Clone
+{
+    type Item = A;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let ScanProj { s, f, acc } = self.project();
+        Poll::Ready(ready!(s.poll_next(cx)).map(|v| {
+            let next = f(acc.take().unwrap(), v);
+            *acc = Some(next.clone());
+            next
+        }))
+    }
+}
+
+/// Scan using the first value as the seed, which is yielded as is.
+#[pin_project(project=ScanSeedlessProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ScanSeedless<S: Stream, F> {
+    #[pin]
+    s: S,
+    f: F,
+    acc: Option<S::Item>,
+}
+
+impl<S, F> ScanSeedless<S, F>
+    where S: Stream, F: FnMut(S::Item, S::Item) -> S::Item, S::Item: Clone
+{
+    pub fn new(s: S, f: F) -> Self {
+        ScanSeedless {
+            s,
+            f,
+            acc: None,
+        }
+    }
+}
+
+impl<S, F> Stream for ScanSeedless<S, F>
+    where S: Stream, F: FnMut(S::Item, S::Item) -> S::Item, S::Item: Clone
+{
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let ScanSeedlessProj { s, f, acc } = self.project();
+        Poll::Ready(ready!(s.poll_next(cx)).map(|v| {
+            let next = match acc.take() {
+                Some(a) => f(a, v),
+                None => v,
+            };
+            *acc = Some(next.clone());
+            next
+        }))
+    }
+}
+
+/// Yield the accumulator once the stream ends, which is the seed if the
+/// stream is empty.
+#[pin_project(project=ReduceProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Reduce<S, F, A> {
+    #[pin]
+    s: Fuse<S>,
+    f: F,
+    acc: Option<A>,
+}
+
+impl<S, F, A> Reduce<S, F, A>
+    where S: Stream, F: FnMut(A, S::Item) -> A
+{
+    pub fn new(s: S, seed: A, f: F) -> Self {
+        Reduce {
+            s: s.fuse(),
+            f,
+            acc: Some(seed),
+        }
+    }
+}
+
+impl<S, F, A> Stream for Reduce<S, F, A>
+    where S: Stream, F: FnMut(A, S::Item) -> A
+{
+    type Item = A;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let ReduceProj { mut s, f, acc } = self.project();
+        while let Some(v) = ready!(s.as_mut().poll_next(cx)) {
+            if let Some(a) = acc.take() {
+                *acc = Some(f(a, v));
+            }
+        }
+        Poll::Ready(acc.take())
+    }
+}
+
+/// Reduce using the first value as the seed. An empty stream ends without
+/// a value, as in rxjs.
+#[pin_project(project=ReduceSeedlessProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct ReduceSeedless<S: Stream, F> {
+    #[pin]
+    s: Fuse<S>,
+    f: F,
+    acc: Option<S::Item>,
+}
+
+impl<S, F> ReduceSeedless<S, F>
+    where S: Stream, F: FnMut(S::Item, S::Item) -> S::Item
+{
+    pub fn new(s: S, f: F) -> Self {
+        ReduceSeedless {
+            s: s.fuse(),
+            f,
+            acc: None,
+        }
+    }
+}
+
+impl<S, F> Stream for ReduceSeedless<S, F>
+    where S: Stream, F: FnMut(S::Item, S::Item) -> S::Item
+{
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let ReduceSeedlessProj { mut s, f, acc } = self.project();
+        while let Some(v) = ready!(s.as_mut().poll_next(cx)) {
+            *acc = Some(match acc.take() {
+                Some(a) => f(a, v),
+                None => v,
+            });
+        }
+        Poll::Ready(acc.take())
+    }
+}
@@ -1,6 +1,5 @@
 use futures::task::Context;
 use super::buffered_stream::{Buffer, BufferedStream};
-use std::mem;
 use futures::{Stream, StreamExt};
 
 #[derive(Default)]
@@ -12,25 +11,25 @@ impl<V> SimpleCountBuffer<V> {
     pub fn new(max_count: usize) -> Self {
         SimpleCountBuffer {
             vec: Vec::with_capacity(max_count),
-            max_count: max_count,
+            max_count,
         }
     }
 }
 impl<V> Buffer for SimpleCountBuffer<V> {
     type V = V;
-    fn insert(&mut self, v:V) -> () {
+    fn insert(&mut self, v:V) {
         self.vec.push(v);
     }
     fn poll_buffer(&mut self, _cx: &mut Context) -> Option<Vec<V>> {
         if self.vec.len() == self.max_count {
-            return Some(mem::replace(&mut self.vec, Vec::new()))
+            Some(std::mem::take(&mut self.vec))
         } else {
-            return None
+            None
         }
     }
     fn poll_buffer_after_done(&mut self, _cx: &mut Context) -> Option<Vec<V>> {
-        if self.vec.len() > 0 {
-            Some(mem::replace(&mut self.vec, Vec::new()))
+        if !self.vec.is_empty() {
+            Some(std::mem::take(&mut self.vec))
         } else {
             None
         }
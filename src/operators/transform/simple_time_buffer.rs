@@ -2,8 +2,8 @@ use futures::task::Context;
 use futures::task::Poll;
 use super::buffered_stream::{Buffer, BufferedStream};
 use futures::{Stream, StreamExt};
-use std::mem;
 use super::super::source;
+use super::super::super::scheduler::{Scheduler, DefaultScheduler};
 use std::pin::Pin;
 use std::ops::{Deref, DerefMut};
 
@@ -14,45 +14,48 @@ pub struct StreamControlledBuffer<V, D> {
 
 impl<V, D> Buffer for StreamControlledBuffer<V, D> where D: DerefMut, <D as Deref>::Target: Stream {
     type V = V;
-    fn insert(&mut self, v:V) -> () {
+    fn insert(&mut self, v:V) {
         self.vec.push(v);
     }
     fn poll_buffer(&mut self, cx: &mut Context<'_>) -> Option<Vec<V>> {
         match self.s.as_mut().poll_next(cx) {
-            Poll::Ready(_) => Some(mem::replace(&mut self.vec, Vec::new())),
+            Poll::Ready(_) => Some(std::mem::take(&mut self.vec)),
             Poll::Pending => None
         }
     }
 }
 
-pub type SimpleTimeBuffer<V> = StreamControlledBuffer<V, Box<source::TimerStream>>;
-pub type SimpleTimeBufferredStream<S> = BufferedStream<S, SimpleTimeBuffer<<S as Stream>::Item>>;
+pub type SimpleTimeBuffer<V, Sch = DefaultScheduler> = StreamControlledBuffer<V, Box<source::TimerStream<Sch>>>;
+pub type SimpleTimeBufferredStream<S, Sch = DefaultScheduler> = BufferedStream<S, SimpleTimeBuffer<<S as Stream>::Item, Sch>>;
 
-pub fn new_simple_time_buffer<V>(time_span: u64) -> SimpleTimeBuffer<V> {
+pub fn new_simple_time_buffer<V, Sch: Scheduler>(time_span: u64, scheduler: Sch) -> SimpleTimeBuffer<V, Sch> {
     StreamControlledBuffer {
         vec: Vec::<V>::new(),
-        s: Box::pin(source::interval(time_span)),
+        s: Box::pin(source::interval_with_scheduler(time_span, scheduler)),
     }
 }
 
 impl<S: Stream> SimpleTimeBufferredStream<S> {
     pub fn new(s: S, time_span: u64) -> Self {
+        SimpleTimeBufferredStream::new_with_scheduler(s, time_span, DefaultScheduler::default())
+    }
+}
+
+impl<S: Stream, Sch: Scheduler> SimpleTimeBufferredStream<S, Sch> {
+    pub fn new_with_scheduler(s: S, time_span: u64, scheduler: Sch) -> Self {
         SimpleTimeBufferredStream {
             s: s.fuse(),
-            buffer: StreamControlledBuffer {
-                vec: vec![],
-                s: Box::pin(source::interval(time_span))
-            },
+            buffer: new_simple_time_buffer(time_span, scheduler),
         }
     }
 }
 
 
-pub type SimpleExternalTimeBuffer<'a, V> = StreamControlledBuffer<V, &'a mut source::TimerStream>;
-pub type SimpleExternalTimeBufferredStream<'a, S> = BufferedStream<S, SimpleExternalTimeBuffer<'a, <S as Stream>::Item>>;
+pub type SimpleExternalTimeBuffer<'a, V, Sch = DefaultScheduler> = StreamControlledBuffer<V, &'a mut source::TimerStream<Sch>>;
+pub type SimpleExternalTimeBufferredStream<'a, S, Sch = DefaultScheduler> = BufferedStream<S, SimpleExternalTimeBuffer<'a, <S as Stream>::Item, Sch>>;
 
-impl<'a, S: Stream> SimpleExternalTimeBufferredStream<'a, S> {
-    pub fn new_with_timer_stream(s: S, control_stream: Pin<&'a mut source::TimerStream>) -> Self {
+impl<'a, S: Stream, Sch: Scheduler> SimpleExternalTimeBufferredStream<'a, S, Sch> {
+    pub fn new_with_timer_stream(s: S, control_stream: Pin<&'a mut source::TimerStream<Sch>>) -> Self {
         SimpleExternalTimeBufferredStream {
             s: s.fuse(),
             buffer: StreamControlledBuffer {
@@ -62,4 +65,3 @@ impl<'a, S: Stream> SimpleExternalTimeBufferredStream<'a, S> {
         }
     }
 }
-
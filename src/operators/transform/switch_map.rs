@@ -0,0 +1,68 @@
+use futures::task::Poll;
+use futures::task::Context;
+use std::pin::Pin;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use pin_project::pin_project;
+
+#[pin_project(project=SwitchMapProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct SwitchMap<S: Stream, F, U> {
+    #[pin]
+    s: Fuse<S>,
+    f: F,
+    #[pin]
+    inner: Option<U>,
+}
+
+impl<S, F, U> SwitchMap<S, F, U> 
+    where S: Stream, F: FnMut(S::Item) -> U, U: Stream
+{
+    pub fn new(s: S, f: F) -> Self {
+        SwitchMap {
+            s: s.fuse(),
+            f,
+            inner: None,
+        }
+    }
+}
+
+impl<S, F, U> Stream for SwitchMap<S, F, U> 
+    where S: Stream, F: FnMut(S::Item) -> U, U: Stream
+{
+    type Item = U::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>, 
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let SwitchMapProj { mut s, f, mut inner } = self.project();
+        // Only the inner stream of the latest outer value is kept. Take one
+        // outer value per call, so the inner stream it makes is polled before
+        // the next one replaces it
+        let outer_ready = match s.as_mut().poll_next(cx) {
+            Poll::Ready(Some(v)) => {
+                inner.set(Some(f(v)));
+                true
+            }
+            Poll::Ready(None) | Poll::Pending => false,
+        };
+        if let Some(current) = inner.as_mut().as_pin_mut() {
+            match current.poll_next(cx) {
+                Poll::Ready(None) => inner.set(None),
+                Poll::Ready(Some(x)) => return Poll::Ready(Some(x)),
+                Poll::Pending => (),
+            }
+        }
+        if outer_ready {
+            // The outer stream hasn't registered the waker, poll it again
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        } else if s.is_done() && inner.is_none() {
+            Poll::Ready(None)
+        } else {
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,65 @@
+use futures::task::Context;
+use futures::task::Poll;
+use super::overlapped_buffer::{BufferCreator, BufferOpener, OverlappedBuffer};
+use super::buffered_stream::BufferedStream;
+use super::notifier_buffer::NotifierBuffer;
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use std::pin::Pin;
+
+#[derive(Debug)]
+pub struct ToggleBufferOpener<O> {
+    openings: Pin<Box<Fuse<O>>>,
+}
+
+#[derive(Debug)]
+pub struct ToggleBufferCreator<F> {
+    closing_selector: F,
+}
+
+impl<O: Stream> ToggleBufferOpener<O> {
+    pub fn new(openings: O) -> Self {
+        ToggleBufferOpener {
+            openings: Box::pin(openings.fuse()),
+        }
+    }
+}
+
+impl<F> ToggleBufferCreator<F> {
+    pub fn new(closing_selector: F) -> Self {
+        ToggleBufferCreator {
+            closing_selector,
+        }
+    }
+}
+
+impl<O: Stream> BufferOpener for ToggleBufferOpener<O> {
+    type Opening = O::Item;
+    fn check_open(&mut self) -> Option<O::Item> {
+        None
+    }
+    fn poll_open(&mut self, cx: &mut Context) -> Option<O::Item> {
+        match self.openings.as_mut().poll_next(cx) {
+            Poll::Ready(Some(opening)) => Some(opening),
+            _ => None,
+        }
+    }
+}
+
+impl<V, T, F: FnMut(T) -> C, C: Stream> BufferCreator<NotifierBuffer<V, C>, T> for ToggleBufferCreator<F> {
+    fn new_buffer(&mut self, opening: T) -> NotifierBuffer<V, C> {
+        NotifierBuffer::new((self.closing_selector)(opening))
+    }
+}
+
+pub type ToggleBuffer<V, O, F, C> = OverlappedBuffer<NotifierBuffer<V, C>, ToggleBufferOpener<O>, ToggleBufferCreator<F>>;
+pub type ToggleBufferedStream<S, O, F, C> = BufferedStream<S, ToggleBuffer<<S as Stream>::Item, O, F, C>>;
+
+impl<S: Stream, O: Stream, F: FnMut(O::Item) -> C, C: Stream> ToggleBufferedStream<S, O, F, C> where S::Item: Clone {
+    pub fn new(s: S, openings: O, closing_selector: F) -> Self {
+        ToggleBufferedStream {
+            s: s.fuse(),
+            buffer: ToggleBuffer::new_internal(ToggleBufferOpener::new(openings), ToggleBufferCreator::new(closing_selector)),
+        }
+    }
+}
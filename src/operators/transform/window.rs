@@ -0,0 +1,314 @@
+use futures::task::Context;
+use futures::task::Poll;
+use super::buffered_stream::Buffer;
+use super::overlapped_buffer::{BufferCreator, BufferOpener, OverlappedBuffer};
+use super::overlapped_count_buffer::{CountBufferCreator, CountBufferOpener};
+use super::overlapped_time_buffer::{TimeBufferCreator, TimeBufferOpener};
+use super::toggle_buffer::{ToggleBufferCreator, ToggleBufferOpener};
+use super::simple_count_buffer::SimpleCountBuffer;
+use super::simple_time_buffer::{new_simple_time_buffer, SimpleTimeBuffer};
+use super::notifier_buffer::NotifierBuffer;
+use super::super::multicasting::share::Waiters;
+use super::super::super::subject::{Subject, Subscription};
+use super::super::super::scheduler::{Scheduler, DefaultScheduler};
+use futures::{Stream, StreamExt};
+use futures::stream::Fuse;
+use std::collections::VecDeque;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+
+// A window is a buffer pushing the values to a subject rather than keeping
+// them. When it closes is told by a buffer of the same kind fed with (),
+// whose yielded vectors are only a signal.
+
+/// Windows which open and close by buffers of (), see WindowedStream.
+pub trait Windows: Buffer {
+    /// The windows opened since the last call, in opening order.
+    fn take_opened(&mut self) -> Option<Subscription<Self::V>>;
+}
+
+/// A window in overlapped windows, closed when the buffer closing it yields.
+pub struct WindowBuffer<T, B> {
+    closing: B,
+    subject: Subject<T>,
+}
+
+impl<T: Clone, B: Buffer<V = ()>> Buffer for WindowBuffer<T, B> {
+    type V = T;
+    fn insert(&mut self, v: T) {
+        self.subject.next(v);
+        self.closing.insert(());
+    }
+    fn poll_buffer(&mut self, cx: &mut Context) -> Option<Vec<T>> {
+        self.closing.poll_buffer(cx).map(|_| {
+            self.subject.complete();
+            Vec::new()
+        })
+    }
+    fn poll_buffer_after_done(&mut self, _cx: &mut Context) -> Option<Vec<T>> {
+        self.subject.complete();
+        None
+    }
+}
+
+/// Create the windows with the closing buffers of the given creator, and
+/// keep them to be yielded.
+pub struct WindowCreator<C, T> {
+    creator: C,
+    opened: VecDeque<Subscription<T>>,
+}
+
+impl<C, T> WindowCreator<C, T> {
+    pub fn new(creator: C) -> Self {
+        WindowCreator {
+            creator,
+            opened: VecDeque::new(),
+        }
+    }
+}
+
+impl<C, B, O, T: Clone> BufferCreator<WindowBuffer<T, B>, O> for WindowCreator<C, T> where C: BufferCreator<B, O> {
+    fn new_buffer(&mut self, opening: O) -> WindowBuffer<T, B> {
+        let subject = Subject::new();
+        self.opened.push_back(subject.subscribe());
+        WindowBuffer {
+            closing: self.creator.new_buffer(opening),
+            subject,
+        }
+    }
+}
+
+impl<T: Clone, B, O, C> Windows for OverlappedBuffer<WindowBuffer<T, B>, O, WindowCreator<C, T>>
+    where B: Buffer<V = ()>, O: BufferOpener, C: BufferCreator<B, O::Opening>
+{
+    fn take_opened(&mut self) -> Option<Subscription<T>> {
+        self.creator.opened.pop_front()
+    }
+}
+
+/// One window after another, the next one opening as soon as the closing
+/// buffer yields.
+pub struct ConsecutiveWindow<T, B> {
+    closing: B,
+    subject: Subject<T>,
+    opened: Option<Subscription<T>>,
+}
+
+impl<T: Clone, B> ConsecutiveWindow<T, B> {
+    pub fn new(closing: B) -> Self {
+        let subject = Subject::new();
+        ConsecutiveWindow {
+            closing,
+            opened: Some(subject.subscribe()),
+            subject,
+        }
+    }
+}
+
+impl<T: Clone, B: Buffer<V = ()>> Buffer for ConsecutiveWindow<T, B> {
+    type V = T;
+    fn insert(&mut self, v: T) {
+        self.subject.next(v);
+        self.closing.insert(());
+    }
+    fn poll_buffer(&mut self, cx: &mut Context) -> Option<Vec<T>> {
+        self.closing.poll_buffer(cx).map(|_| {
+            self.subject.complete();
+            self.subject = Subject::new();
+            self.opened = Some(self.subject.subscribe());
+            Vec::new()
+        })
+    }
+    fn poll_buffer_after_done(&mut self, _cx: &mut Context) -> Option<Vec<T>> {
+        self.subject.complete();
+        None
+    }
+}
+
+impl<T: Clone, B: Buffer<V = ()>> Windows for ConsecutiveWindow<T, B> {
+    fn take_opened(&mut self) -> Option<Subscription<T>> {
+        self.opened.take()
+    }
+}
+
+/// What the windowed stream and its windows share, the upstream is polled by
+/// whichever of them needs the next value.
+struct WindowState<S: Stream, W> {
+    s: Pin<Box<Fuse<S>>>,
+    windows: W,
+    waiters: Arc<Waiters>,
+}
+
+impl<S: Stream, W: Windows<V = S::Item>> WindowState<S, W> {
+    /// Ready once a value was pushed to the windows, or a window opened or
+    /// closed, Pending when nothing can change before a wakeup.
+    fn drive(&mut self, cx: &mut Context<'_>) -> Poll<()> {
+        if self.s.is_done() {
+            return Poll::Pending
+        }
+        self.waiters.register(cx.waker());
+        let waker = futures::task::waker(self.waiters.clone());
+        let mut cx = Context::from_waker(&waker);
+        // A closed window may open the next one
+        if self.windows.poll_buffer(&mut cx).is_some() {
+            return Poll::Ready(())
+        }
+        match self.s.as_mut().poll_next(&mut cx) {
+            Poll::Ready(Some(v)) => self.windows.insert(v),
+            Poll::Ready(None) => while self.windows.poll_buffer_after_done(&mut cx).is_some() {},
+            Poll::Pending => return Poll::Pending,
+        }
+        // The others may be waiting for this value
+        waker.wake_by_ref();
+        Poll::Ready(())
+    }
+}
+
+/// Stream yielding a stream per window as soon as it opens, which yields
+/// the values as they come and ends when the window closes. All windows end
+/// when the stream ends. A window dropped unread misses its values only.
+/// The upstream is polled by whichever of this stream and the windows needs
+/// the next value, so the windows can be read one after another, with then
+/// or concat_map, as well as concurrently with merge_map.
+#[must_use = "streams do nothing unless polled"]
+pub struct WindowedStream<S: Stream, W: Windows<V = S::Item>> {
+    state: Arc<Mutex<WindowState<S, W>>>,
+}
+
+impl<S: Stream, W: Windows<V = S::Item>> WindowedStream<S, W> {
+    pub fn new_internal(s: S, windows: W) -> Self {
+        WindowedStream {
+            state: Arc::new(Mutex::new(WindowState {
+                s: Box::pin(s.fuse()),
+                windows,
+                waiters: Arc::default(),
+            })),
+        }
+    }
+}
+
+impl<S: Stream, W: Windows<V = S::Item>> Stream for WindowedStream<S, W> {
+    type Item = Window<S, W>;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(subscription) = state.windows.take_opened() {
+                return Poll::Ready(Some(Window {
+                    state: self.state.clone(),
+                    subscription,
+                }))
+            }
+            if state.s.is_done() {
+                return Poll::Ready(None)
+            }
+            ready!(state.drive(cx));
+        }
+    }
+}
+
+/// The values of one window, see WindowedStream.
+#[must_use = "streams do nothing unless polled"]
+pub struct Window<S: Stream, W: Windows<V = S::Item>> {
+    state: Arc<Mutex<WindowState<S, W>>>,
+    subscription: Subscription<S::Item>,
+}
+
+impl<S: Stream, W: Windows<V = S::Item>> Stream for Window<S, W> {
+    type Item = S::Item;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        loop {
+            if let Poll::Ready(v) = Pin::new(&mut self.subscription).poll_next(cx) {
+                return Poll::Ready(v)
+            }
+            ready!(self.state.lock().unwrap().drive(cx));
+        }
+    }
+}
+
+pub type CountWindowedStream<S> = WindowedStream<S, ConsecutiveWindow<<S as Stream>::Item, SimpleCountBuffer<()>>>;
+
+impl<S: Stream> CountWindowedStream<S> where S::Item: Clone {
+    pub fn new(s: S, max_count: usize) -> Self {
+        CountWindowedStream::new_internal(s, ConsecutiveWindow::new(SimpleCountBuffer::new(max_count)))
+    }
+}
+
+pub type OverlappedCountWindows<T> = OverlappedBuffer<WindowBuffer<T, SimpleCountBuffer<()>>, CountBufferOpener, WindowCreator<CountBufferCreator, T>>;
+pub type OverlappedCountWindowedStream<S> = WindowedStream<S, OverlappedCountWindows<<S as Stream>::Item>>;
+
+impl<S: Stream> OverlappedCountWindowedStream<S> where S::Item: Clone {
+    pub fn new(s: S, max_count: usize, skip: usize) -> Self {
+        let mut windows = OverlappedCountWindows::new_internal(
+            CountBufferOpener::new(skip),
+            WindowCreator::new(CountBufferCreator::new(max_count)),
+        );
+        let w = windows.creator.new_buffer(());
+        windows.buffers.push_back(w);
+        OverlappedCountWindowedStream::new_internal(s, windows)
+    }
+}
+
+pub type TimeWindowedStream<S, Sch = DefaultScheduler> = WindowedStream<S, ConsecutiveWindow<<S as Stream>::Item, SimpleTimeBuffer<(), Sch>>>;
+
+impl<S: Stream> TimeWindowedStream<S> where S::Item: Clone {
+    pub fn new(s: S, time_span: u64) -> Self {
+        TimeWindowedStream::new_with_scheduler(s, time_span, DefaultScheduler::default())
+    }
+}
+
+impl<S: Stream, Sch: Scheduler> TimeWindowedStream<S, Sch> where S::Item: Clone {
+    pub fn new_with_scheduler(s: S, time_span: u64, scheduler: Sch) -> Self {
+        TimeWindowedStream::new_internal(s, ConsecutiveWindow::new(new_simple_time_buffer(time_span, scheduler)))
+    }
+}
+
+pub type OverlappedTimeWindows<T, Sch = DefaultScheduler> =
+    OverlappedBuffer<WindowBuffer<T, SimpleTimeBuffer<(), Sch>>, TimeBufferOpener<Sch>, WindowCreator<TimeBufferCreator<Sch>, T>>;
+pub type OverlappedTimeWindowedStream<S, Sch = DefaultScheduler> = WindowedStream<S, OverlappedTimeWindows<<S as Stream>::Item, Sch>>;
+
+impl<S: Stream> OverlappedTimeWindowedStream<S> where S::Item: Clone {
+    pub fn new(s: S, time_span: u64, creation_interval: u64) -> Self {
+        OverlappedTimeWindowedStream::new_with_scheduler(s, time_span, creation_interval, DefaultScheduler::default())
+    }
+}
+
+impl<S: Stream, Sch: Scheduler> OverlappedTimeWindowedStream<S, Sch> where S::Item: Clone {
+    pub fn new_with_scheduler(s: S, time_span: u64, creation_interval: u64, scheduler: Sch) -> Self {
+        let mut windows = OverlappedTimeWindows::new_internal(
+            TimeBufferOpener::new(creation_interval, scheduler.clone()),
+            WindowCreator::new(TimeBufferCreator::new(time_span, scheduler)),
+        );
+        let w = windows.creator.new_buffer(());
+        windows.buffers.push_back(w);
+        OverlappedTimeWindowedStream::new_internal(s, windows)
+    }
+}
+
+pub type NotifierWindowedStream<S, N> = WindowedStream<S, ConsecutiveWindow<<S as Stream>::Item, NotifierBuffer<(), N>>>;
+
+impl<S: Stream, N: Stream> NotifierWindowedStream<S, N> where S::Item: Clone {
+    pub fn new(s: S, notifier: N) -> Self {
+        NotifierWindowedStream::new_internal(s, ConsecutiveWindow::new(NotifierBuffer::new(notifier)))
+    }
+}
+
+pub type ToggleWindows<T, O, F, C> =
+    OverlappedBuffer<WindowBuffer<T, NotifierBuffer<(), C>>, ToggleBufferOpener<O>, WindowCreator<ToggleBufferCreator<F>, T>>;
+pub type ToggleWindowedStream<S, O, F, C> = WindowedStream<S, ToggleWindows<<S as Stream>::Item, O, F, C>>;
+
+impl<S: Stream, O: Stream, F: FnMut(O::Item) -> C, C: Stream> ToggleWindowedStream<S, O, F, C> where S::Item: Clone {
+    pub fn new(s: S, openings: O, closing_selector: F) -> Self {
+        ToggleWindowedStream::new_internal(s, ToggleWindows::new_internal(
+            ToggleBufferOpener::new(openings),
+            WindowCreator::new(ToggleBufferCreator::new(closing_selector)),
+        ))
+    }
+}
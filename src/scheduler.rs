@@ -0,0 +1,235 @@
+use futures::task::{ArcWake, Context, Poll, Waker};
+use std::collections::BTreeMap;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+/// The clock every time based source and operator is built on.
+/// It tells the current time and creates futures that resolve at a deadline,
+/// which is all `source::timer` and the time based buffers need.
+pub trait Scheduler: Clone {
+    type Delay: Future<Output = ()>;
+    fn now(&self) -> Instant;
+    fn delay_until(&self, deadline: Instant) -> Self::Delay;
+}
+
+/// Scheduler reading the real clock through tokio's timer, so it needs to run
+/// inside a tokio runtime. Enabled by the tokio feature, which is on by default.
+#[cfg(feature = "tokio")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TokioScheduler;
+
+#[cfg(feature = "tokio")]
+impl Scheduler for TokioScheduler {
+    type Delay = tokio::time::Sleep;
+
+    fn now(&self) -> Instant {
+        tokio::time::Instant::now().into_std()
+    }
+
+    fn delay_until(&self, deadline: Instant) -> Self::Delay {
+        tokio::time::sleep_until(tokio::time::Instant::from_std(deadline))
+    }
+}
+
+/// Scheduler reading the real clock through futures-timer, which runs its own
+/// timer thread and works with any executor, async-std included.
+/// Enabled by the futures-timer feature.
+#[cfg(feature = "futures-timer")]
+#[derive(Debug, Clone, Copy, Default)]
+pub struct FuturesTimerScheduler;
+
+#[cfg(feature = "futures-timer")]
+impl Scheduler for FuturesTimerScheduler {
+    type Delay = futures_timer::Delay;
+
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    fn delay_until(&self, deadline: Instant) -> Self::Delay {
+        futures_timer::Delay::new(deadline.saturating_duration_since(Instant::now()))
+    }
+}
+
+/// The scheduler used by the sources and operators which don't take one.
+/// It's TokioScheduler when the tokio feature is on, otherwise FuturesTimerScheduler.
+#[cfg(feature = "tokio")]
+pub type DefaultScheduler = TokioScheduler;
+#[cfg(all(not(feature = "tokio"), feature = "futures-timer"))]
+pub type DefaultScheduler = FuturesTimerScheduler;
+#[cfg(not(any(feature = "tokio", feature = "futures-timer")))]
+compile_error!("rxstream needs a timer backend, enable either the tokio or the futures-timer feature");
+
+/// Scheduler whose time only moves when it is told to.
+/// Cloned handles share the same clock, so a test can hand one clone to the
+/// stream under test and drive the time with another one.
+/// Notes
+/// 1. Time never moves by itself. Use advance_by/advance_to, or run a future
+///    to completion with run, which jumps from one pending deadline to the next.
+/// 2. Nothing here needs a runtime, so tests can be plain #[test] functions.
+#[derive(Debug, Clone)]
+pub struct VirtualTimeScheduler {
+    clock: Arc<Mutex<VirtualClock>>,
+}
+
+#[derive(Debug)]
+struct VirtualClock {
+    start: Instant,
+    now: Instant,
+    next_id: u64,
+    // Keyed by deadline first so the earliest timer is always in front.
+    // A timer without waker has not been polled yet, or has already been woken.
+    timers: BTreeMap<(Instant, u64), Option<Waker>>,
+}
+
+impl VirtualTimeScheduler {
+    pub fn new() -> Self {
+        let start = Instant::now();
+        VirtualTimeScheduler {
+            clock: Arc::new(Mutex::new(VirtualClock {
+                start,
+                now: start,
+                next_id: 0,
+                timers: BTreeMap::new(),
+            })),
+        }
+    }
+
+    /// Time passed since the scheduler was created.
+    pub fn elapsed(&self) -> Duration {
+        let clock = self.clock.lock().unwrap();
+        clock.now - clock.start
+    }
+
+    pub fn advance_by(&self, duration: Duration) {
+        let target = self.now() + duration;
+        self.advance_to(target)
+    }
+
+    /// Move the clock to the deadline and wake every timer due by then.
+    /// The clock never goes backwards, an earlier deadline is a no-op.
+    pub fn advance_to(&self, deadline: Instant) {
+        let wakers = {
+            let mut clock = self.clock.lock().unwrap();
+            if deadline > clock.now {
+                clock.now = deadline;
+            }
+            let now = clock.now;
+            clock.timers.iter_mut()
+                .take_while(|(key, _)| key.0 <= now)
+                .filter_map(|(_, waker)| waker.take())
+                .collect::<Vec<_>>()
+        };
+        // Wake outside of the lock, as a waker may poll a delay right away
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+
+    /// The earliest deadline somebody is waiting for.
+    fn next_deadline(&self) -> Option<Instant> {
+        let clock = self.clock.lock().unwrap();
+        clock.timers.iter()
+            .find(|(_, waker)| waker.is_some())
+            .map(|(key, _)| key.0)
+    }
+
+    /// Drive the future to completion, advancing the clock to the next pending
+    /// deadline whenever the future can not make progress otherwise.
+    /// Panics if the future is pending while no timer is pending, since
+    /// nothing would ever wake it up.
+    pub fn run<F: Future>(&self, f: F) -> F::Output {
+        let mut f = Box::pin(f);
+        match self.run_until_stalled(&mut f) {
+            Poll::Ready(r) => r,
+            Poll::Pending => panic!("future is pending but no timer is pending on the virtual time scheduler"),
+        }
+    }
+
+    /// Same as run, but return Pending instead of panic once the future is
+    /// pending and no timer is pending. The future can be resumed later.
+    pub fn run_until_stalled<F: Future + Unpin>(&self, f: &mut F) -> Poll<F::Output> {
+        let flag = Arc::new(WokenFlag(AtomicBool::new(true)));
+        let waker = futures::task::waker(flag.clone());
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            if flag.0.swap(false, Ordering::SeqCst) {
+                if let Poll::Ready(r) = Pin::new(&mut *f).poll(&mut cx) {
+                    return Poll::Ready(r)
+                }
+                continue
+            }
+            match self.next_deadline() {
+                Some(deadline) => self.advance_to(deadline),
+                None => return Poll::Pending,
+            }
+        }
+    }
+}
+
+impl Default for VirtualTimeScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scheduler for VirtualTimeScheduler {
+    type Delay = VirtualDelay;
+
+    fn now(&self) -> Instant {
+        self.clock.lock().unwrap().now
+    }
+
+    fn delay_until(&self, deadline: Instant) -> Self::Delay {
+        let mut clock = self.clock.lock().unwrap();
+        let id = clock.next_id;
+        clock.next_id += 1;
+        clock.timers.insert((deadline, id), None);
+        VirtualDelay {
+            clock: self.clock.clone(),
+            key: (deadline, id),
+        }
+    }
+}
+
+/// Future resolved once the virtual clock reaches its deadline.
+#[derive(Debug)]
+#[must_use = "futures do nothing unless polled"]
+pub struct VirtualDelay {
+    clock: Arc<Mutex<VirtualClock>>,
+    key: (Instant, u64),
+}
+
+impl Future for VirtualDelay {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<()> {
+        let mut clock = self.clock.lock().unwrap();
+        if clock.now >= self.key.0 {
+            clock.timers.remove(&self.key);
+            Poll::Ready(())
+        } else {
+            clock.timers.insert(self.key, Some(cx.waker().clone()));
+            Poll::Pending
+        }
+    }
+}
+
+impl Drop for VirtualDelay {
+    fn drop(&mut self) {
+        if let Ok(mut clock) = self.clock.lock() {
+            clock.timers.remove(&self.key);
+        }
+    }
+}
+
+struct WokenFlag(AtomicBool);
+
+impl ArcWake for WokenFlag {
+    fn wake_by_ref(arc_self: &Arc<Self>) {
+        arc_self.0.store(true, Ordering::SeqCst);
+    }
+}
@@ -1,36 +1,194 @@
-use futures::stream::{Stream, StreamExt};
-use tokio::time;
-use std::time::{Duration};
+use futures::stream::{Iter, Once, Pending, Repeat, Stream};
+use futures::future::Ready;
+use futures::task::{Context, Poll};
+use futures::Future;
+use std::iter::StepBy;
+use std::ops::Range;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+use pin_project::pin_project;
+use super::scheduler::{Scheduler, DefaultScheduler};
 
-pub type TimerStream = impl StreamExt<Item = u64>;
+/// Stream emitting 0, 1, 2... The first value is emitted after the initial
+/// delay, and each following value one period after the previous one.
+/// If the consumer falls behind, the missed values are emitted right away.
+#[pin_project(project=TimerStreamProj)]
+#[must_use = "streams do nothing unless polled"]
+pub struct TimerStream<Sch: Scheduler = DefaultScheduler> {
+    scheduler: Sch,
+    period: Duration,
+    deadline: Instant,
+    count: u64,
+    #[pin]
+    delay: Sch::Delay,
+}
+
+impl<Sch: Scheduler> TimerStream<Sch> {
+    pub fn new(initial: u64, period: u64, scheduler: Sch) -> Self {
+        assert!(period > 0, "timer period must be non-zero");
+        let deadline = scheduler.now() + Duration::from_millis(initial);
+        TimerStream {
+            delay: scheduler.delay_until(deadline),
+            scheduler,
+            period: Duration::from_millis(period),
+            deadline,
+            count: 0,
+        }
+    }
+}
+
+impl<Sch: Scheduler> Stream for TimerStream<Sch> {
+    type Item = u64;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        ready!(this.delay.as_mut().poll(cx));
+        let count = *this.count;
+        *this.count += 1;
+        *this.deadline += *this.period;
+        this.delay.set(this.scheduler.delay_until(*this.deadline));
+        Poll::Ready(Some(count))
+    }
+}
 
 pub fn timer(initial: u64, period: u64) -> TimerStream {
-    let iter = 0u64..;
-    let b = time::interval_at(
-        time::Instant::now() + Duration::from_millis(initial), 
-        Duration::from_millis(period),
-    ).zip(futures::stream::iter(iter)).map(|r| r.1);
-    b
+    timer_with_scheduler(initial, period, DefaultScheduler::default())
 }
 
+pub fn timer_with_scheduler<Sch: Scheduler>(initial: u64, period: u64, scheduler: Sch) -> TimerStream<Sch> {
+    TimerStream::new(initial, period, scheduler)
+}
 
 pub fn interval(millis: u64) -> TimerStream {
     timer(millis, millis)
 }
 
+pub fn interval_with_scheduler<Sch: Scheduler>(millis: u64, scheduler: Sch) -> TimerStream<Sch> {
+    timer_with_scheduler(millis, millis, scheduler)
+}
+
 /// Interval which emit the first value immediately rather than wait for the first period pass
 pub fn interval_immediate(millis: u64) -> TimerStream {
     timer(0, millis)
 }
 
-/// This is for both of and range in rxjs
-pub fn of<T: IntoIterator>(iter: T) -> impl Stream<Item = T::Item> {
+pub fn interval_immediate_with_scheduler<Sch: Scheduler>(millis: u64, scheduler: Sch) -> TimerStream<Sch> {
+    timer_with_scheduler(0, millis, scheduler)
+}
+
+/// This is for of and from in rxjs, see range for a range with a step.
+pub fn of<T: IntoIterator>(iter: T) -> Iter<T::IntoIter> {
     futures::stream::iter(iter)
 }
 
-// /// create a stream which emit error immediately
-// pub fn throw_error<E, S: Stream<Error=E>>(error: E) -> futures::stream::Once<S::Item, E> {
-//     futures::stream::once::<S::Item, E>(Err(error))
-// } 
+pub type RangeStream<T> = Iter<StepBy<Range<T>>>;
+
+/// Stream of start, start + step, ... up to but excluding end.
+/// Unlike range in rxjs, which takes a count, it follows rust's ranges.
+/// Panics if step is 0.
+pub fn range<T>(start: T, end: T, step: usize) -> RangeStream<T> where Range<T>: Iterator {
+    futures::stream::iter((start..end).step_by(step))
+}
+
+pub type ThrowError<T, E> = Once<Ready<Result<T, E>>>;
 
-pub use futures::stream::empty;
\ No newline at end of file
+/// Create a stream which yields the error immediately and ends.
+pub fn throw_error<T, E>(error: E) -> ThrowError<T, E> {
+    futures::stream::once(futures::future::ready(Err(error)))
+}
+
+pub use futures::stream::empty;
+
+/// Stream which never yields and never ends.
+pub fn never<T>() -> Pending<T> {
+    futures::stream::pending()
+}
+
+/// Stream yielding clones of the value forever. Use take to limit it.
+pub fn repeat_value<T: Clone>(value: T) -> Repeat<T> {
+    futures::stream::repeat(value)
+}
+
+/// Stream created by the factory when it is first polled, see defer.
+#[pin_project(project=DeferProj)]
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Defer<F, S> {
+    factory: Option<F>,
+    #[pin]
+    s: Option<S>,
+}
+
+/// Create the stream lazily. As a stream is polled by one consumer only,
+/// the factory is called once, on the first poll rather than on creation.
+pub fn defer<F: FnOnce() -> S, S: Stream>(factory: F) -> Defer<F, S> {
+    Defer {
+        factory: Some(factory),
+        s: None,
+    }
+}
+
+impl<F: FnOnce() -> S, S: Stream> Stream for Defer<F, S> {
+    type Item = S::Item;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut this = self.project();
+        if let Some(factory) = this.factory.take() {
+            this.s.set(Some(factory()));
+        }
+        this.s.as_pin_mut().unwrap().poll_next(cx)
+    }
+}
+
+/// Stream of a loop, see generate.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Generate<St, C, I, R> {
+    state: Option<St>,
+    condition: C,
+    iterate: I,
+    result_selector: R,
+}
+
+/// Like a for loop, starting from the initial state, yield the result of
+/// each state while the condition holds, and iterate to the next state.
+/// Use an identity result selector, e.g. |s| *s, to yield the states.
+pub fn generate<St, T, C, I, R>(initial: St, condition: C, iterate: I, result_selector: R) -> Generate<St, C, I, R>
+    where C: FnMut(&St) -> bool, I: FnMut(&St) -> St, R: FnMut(&St) -> T
+{
+    Generate {
+        state: Some(initial),
+        condition,
+        iterate,
+        result_selector,
+    }
+}
+
+// Nothing is pinned, the state is moved on each poll
+impl<St, C, I, R> Unpin for Generate<St, C, I, R> {}
+
+impl<St, T, C, I, R> Stream for Generate<St, C, I, R>
+    where C: FnMut(&St) -> bool, I: FnMut(&St) -> St, R: FnMut(&St) -> T
+{
+    type Item = T;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        let state = match this.state.take() {
+            Some(state) if (this.condition)(&state) => state,
+            _ => return Poll::Ready(None),
+        };
+        let item = (this.result_selector)(&state);
+        this.state = Some((this.iterate)(&state));
+        Poll::Ready(Some(item))
+    }
+}
@@ -0,0 +1,298 @@
+//! Subjects push values imperatively with next, complete and error, and fan
+//! them out to any number of subscribers. Every subscriber has its own queue,
+//! so a slow one never makes the others miss values.
+//! Errors follow the Result convention of RxTryStreamEx: subjects of Result
+//! get an error method, which yields Err to the subscribers and completes.
+//! Late subscribers of a completed subject see the error as well, as in rxjs.
+use futures::task::{Context, Poll, Waker};
+use futures::{Sink, Stream};
+use std::collections::VecDeque;
+use std::convert::Infallible;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, Weak};
+
+#[derive(Debug)]
+pub(crate) enum Kind {
+    Plain,
+    Behavior,
+    Replay(usize),
+    Async,
+}
+
+#[derive(Debug)]
+struct Queue<T> {
+    items: VecDeque<T>,
+    done: bool,
+    waker: Option<Waker>,
+}
+
+#[derive(Debug)]
+pub(crate) struct Core<T> {
+    kind: Kind,
+    subscribers: Vec<Weak<Mutex<Queue<T>>>>,
+    // What a new subscriber gets first, the meaning depends on the kind
+    replay: VecDeque<T>,
+    // Some once completed, with the error if it ended with one
+    terminal: Option<Option<T>>,
+}
+
+impl<T: Clone> Core<T> {
+    pub(crate) fn new(kind: Kind, replay: VecDeque<T>) -> Arc<Mutex<Self>> {
+        Arc::new(Mutex::new(Core {
+            kind,
+            subscribers: Vec::new(),
+            replay,
+            terminal: None,
+        }))
+    }
+
+    fn broadcast(&mut self, item: Option<&T>, done: bool) {
+        self.subscribers.retain(|s| {
+            let s = match s.upgrade() {
+                Some(s) => s,
+                // The subscription is dropped
+                None => return false,
+            };
+            let waker = {
+                let mut q = s.lock().unwrap();
+                if let Some(item) = item {
+                    q.items.push_back(item.clone());
+                }
+                q.done |= done;
+                q.waker.take()
+            };
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+            true
+        });
+    }
+
+    pub(crate) fn next(&mut self, item: T) {
+        if self.terminal.is_some() {
+            return
+        }
+        let capacity = match self.kind {
+            Kind::Plain => 0,
+            Kind::Behavior | Kind::Async => 1,
+            Kind::Replay(size) => size,
+        };
+        if !matches!(self.kind, Kind::Async) {
+            self.broadcast(Some(&item), false);
+        }
+        if capacity > 0 {
+            if self.replay.len() == capacity {
+                self.replay.pop_front();
+            }
+            self.replay.push_back(item);
+        }
+    }
+
+    pub(crate) fn complete(&mut self, error: Option<T>) {
+        if self.terminal.is_some() {
+            return
+        }
+        match (&self.kind, &error) {
+            (Kind::Async, None) => {
+                let last = self.replay.back().cloned();
+                self.broadcast(last.as_ref(), false);
+            }
+            // The last value of an AsyncSubject is only for a completion
+            (Kind::Async, Some(_)) | (Kind::Behavior, _) => self.replay.clear(),
+            _ => (),
+        }
+        self.broadcast(error.as_ref(), true);
+        self.terminal = Some(error);
+    }
+
+    pub(crate) fn subscribe(core: &Arc<Mutex<Self>>) -> Subscription<T> {
+        let mut this = core.lock().unwrap();
+        let mut items = VecDeque::new();
+        if this.terminal.is_some() || !matches!(this.kind, Kind::Async) {
+            items.extend(this.replay.iter().cloned());
+        }
+        if let Some(Some(error)) = &this.terminal {
+            items.push_back(error.clone());
+        }
+        let queue = Arc::new(Mutex::new(Queue {
+            items,
+            done: this.terminal.is_some(),
+            waker: None,
+        }));
+        this.subscribers.push(Arc::downgrade(&queue));
+        Subscription { queue }
+    }
+}
+
+/// Stream of what a subject pushes after subscribe. Dropping it unsubscribes.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct Subscription<T> {
+    queue: Arc<Mutex<Queue<T>>>,
+}
+
+impl<T> Stream for Subscription<T> {
+    type Item = T;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let mut q = self.queue.lock().unwrap();
+        if let Some(item) = q.items.pop_front() {
+            Poll::Ready(Some(item))
+        } else if q.done {
+            Poll::Ready(None)
+        } else {
+            q.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+/// Generate the sink side shared by all the subjects. Clones of a subject
+/// push to the same subscribers.
+macro_rules! subject_sink {
+    ($name:ident) => {
+        impl<T: Clone> $name<T> {
+            pub fn next(&self, item: T) {
+                self.core.lock().unwrap().next(item)
+            }
+
+            /// End all subscriptions. Values pushed afterwards are ignored.
+            pub fn complete(&self) {
+                self.core.lock().unwrap().complete(None)
+            }
+
+            pub fn subscribe(&self) -> Subscription<T> {
+                Core::subscribe(&self.core)
+            }
+        }
+
+        impl<V: Clone, E: Clone> $name<Result<V, E>> {
+            /// Yield the error to all subscriptions and end them.
+            pub fn error(&self, error: E) {
+                self.core.lock().unwrap().complete(Some(Err(error)))
+            }
+        }
+
+        impl<T> Clone for $name<T> {
+            fn clone(&self) -> Self {
+                $name {
+                    core: self.core.clone(),
+                }
+            }
+        }
+
+        /// Forward a stream into the subject, closing the sink completes it.
+        impl<T: Clone> Sink<T> for $name<T> {
+            type Error = Infallible;
+
+            fn poll_ready(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn start_send(self: Pin<&mut Self>, item: T) -> Result<(), Self::Error> {
+                self.next(item);
+                Ok(())
+            }
+
+            fn poll_flush(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                Poll::Ready(Ok(()))
+            }
+
+            fn poll_close(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+                self.complete();
+                Poll::Ready(Ok(()))
+            }
+        }
+    };
+}
+
+/// Subscribers get the values pushed after they subscribe.
+#[derive(Debug)]
+pub struct Subject<T> {
+    core: Arc<Mutex<Core<T>>>,
+}
+
+impl<T: Clone> Subject<T> {
+    pub fn new() -> Self {
+        Subject {
+            core: Core::new(Kind::Plain, VecDeque::new()),
+        }
+    }
+}
+
+impl<T: Clone> Default for Subject<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+subject_sink!(Subject);
+
+/// Subscribers get the current value first, which is the initial value or
+/// the last pushed one. Once completed, subscribers get nothing.
+#[derive(Debug)]
+pub struct BehaviorSubject<T> {
+    core: Arc<Mutex<Core<T>>>,
+}
+
+impl<T: Clone> BehaviorSubject<T> {
+    pub fn new(initial: T) -> Self {
+        BehaviorSubject {
+            core: Core::new(Kind::Behavior, VecDeque::from(vec![initial])),
+        }
+    }
+
+    /// The current value, None once completed.
+    pub fn value(&self) -> Option<T> {
+        self.core.lock().unwrap().replay.back().cloned()
+    }
+}
+
+subject_sink!(BehaviorSubject);
+
+/// Subscribers get up to buffer_size last pushed values first, even after
+/// it completed.
+#[derive(Debug)]
+pub struct ReplaySubject<T> {
+    core: Arc<Mutex<Core<T>>>,
+}
+
+impl<T: Clone> ReplaySubject<T> {
+    pub fn new(buffer_size: usize) -> Self {
+        ReplaySubject {
+            core: Core::new(Kind::Replay(buffer_size), VecDeque::new()),
+        }
+    }
+
+    /// Replay every value pushed so far.
+    pub fn unbounded() -> Self {
+        Self::new(usize::MAX)
+    }
+}
+
+subject_sink!(ReplaySubject);
+
+/// Subscribers get only the last pushed value, when it completes.
+#[derive(Debug)]
+pub struct AsyncSubject<T> {
+    core: Arc<Mutex<Core<T>>>,
+}
+
+impl<T: Clone> AsyncSubject<T> {
+    pub fn new() -> Self {
+        AsyncSubject {
+            core: Core::new(Kind::Async, VecDeque::new()),
+        }
+    }
+}
+
+impl<T: Clone> Default for AsyncSubject<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+subject_sink!(AsyncSubject);
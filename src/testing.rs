@@ -0,0 +1,257 @@
+//! Marble testing, following the marble syntax of rxjs.
+//! A marble string describes what happens on a stream frame by frame, and one
+//! frame is one millisecond of the virtual time of the TestScheduler.
+//! - ' ' whitespace is ignored, it can be used to align marbles
+//! - '-' one frame passes without event
+//! - '|' the stream completes
+//! - '#' the stream yields an error and then completes (fallible streams only)
+//! - '^' the subscription point of a hot stream, which is the frame 0
+//! - '(ab)' all events inside the parentheses happen in the same frame
+//! - any other character is a value, looked up from the given values
+//!
+//! Every character except whitespace takes one frame, including the
+//! parentheses of a group, so "(ab)-c" emits c at frame 5 as in rxjs.
+//! A stream without '|' never completes.
+use futures::task::{Context, Poll};
+use futures::{Future, Stream};
+use std::collections::VecDeque;
+use std::fmt::Debug;
+use std::pin::Pin;
+use std::time::{Duration, Instant};
+use super::scheduler::{Scheduler, VirtualDelay, VirtualTimeScheduler};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Notification<T> {
+    Next(T),
+    Complete,
+}
+
+/// Events at their frames, as parsed from a marble or recorded from a stream.
+pub type Recorded<T> = Vec<(u64, Notification<T>)>;
+
+/// Parse a marble of an infallible stream. Panics on '#'.
+pub fn parse_marble<T: Clone>(marble: &str, values: &[(char, T)]) -> Recorded<T> {
+    parse(marble, |c| lookup(values, c), || panic!("'#' needs a fallible stream, use the *_result functions"))
+}
+
+/// Parse a marble of a fallible stream. Values are wrapped in Ok, and '#'
+/// yields Err(error) followed by the completion.
+pub fn parse_marble_result<T: Clone, E: Clone>(
+    marble: &str,
+    values: &[(char, T)],
+    error: E,
+) -> Recorded<Result<T, E>> {
+    parse(marble, |c| Ok(lookup(values, c)), || Err(error.clone()))
+}
+
+fn lookup<T: Clone>(values: &[(char, T)], c: char) -> T {
+    match values.iter().find(|(k, _)| *k == c) {
+        Some((_, v)) => v.clone(),
+        None => panic!("no value given for '{}' in marble", c),
+    }
+}
+
+fn parse<T>(marble: &str, mut value: impl FnMut(char) -> T, mut error: impl FnMut() -> T) -> Recorded<T> {
+    let mut events = Vec::new();
+    let mut frame = 0i64;
+    let mut zero = 0i64;
+    let mut group: Option<i64> = None;
+    for c in marble.chars() {
+        let at = group.unwrap_or(frame);
+        match c {
+            ' ' => continue,
+            '-' => (),
+            '^' => zero = frame,
+            '(' => group = Some(frame),
+            ')' => group = None,
+            '|' => events.push((at, Notification::Complete)),
+            '#' => {
+                events.push((at, Notification::Next(error())));
+                events.push((at, Notification::Complete));
+            }
+            c => events.push((at, Notification::Next(value(c)))),
+        }
+        frame += 1;
+    }
+    // Events before the subscription point of a hot stream are never seen
+    events.into_iter()
+        .filter(|(at, _)| *at >= zero)
+        .map(|(at, n)| ((at - zero) as u64, n))
+        .collect()
+}
+
+/// Virtual time scheduler for marble tests. Pass a clone of it to the time
+/// based sources and operators under test so they share the same frames.
+#[derive(Debug, Clone)]
+pub struct TestScheduler {
+    scheduler: VirtualTimeScheduler,
+    start: Instant,
+}
+
+impl TestScheduler {
+    pub fn new() -> Self {
+        let scheduler = VirtualTimeScheduler::new();
+        TestScheduler {
+            start: scheduler.now(),
+            scheduler,
+        }
+    }
+
+    /// Stream which starts its frames when it is first polled.
+    pub fn cold<T: Clone>(&self, marble: &str, values: &[(char, T)]) -> TestStream<T> {
+        self.stream(parse_marble(marble, values), None)
+    }
+
+    /// Stream which starts its frames at the creation of the scheduler,
+    /// no matter when it is polled. Values emitted before the first poll are lost.
+    pub fn hot<T: Clone>(&self, marble: &str, values: &[(char, T)]) -> TestStream<T> {
+        self.stream(parse_marble(marble, values), Some(self.start))
+    }
+
+    pub fn cold_result<T: Clone, E: Clone>(
+        &self,
+        marble: &str,
+        values: &[(char, T)],
+        error: E,
+    ) -> TestStream<Result<T, E>> {
+        self.stream(parse_marble_result(marble, values, error), None)
+    }
+
+    pub fn hot_result<T: Clone, E: Clone>(
+        &self,
+        marble: &str,
+        values: &[(char, T)],
+        error: E,
+    ) -> TestStream<Result<T, E>> {
+        self.stream(parse_marble_result(marble, values, error), Some(self.start))
+    }
+
+    fn stream<T>(&self, events: Recorded<T>, origin: Option<Instant>) -> TestStream<T> {
+        TestStream {
+            scheduler: self.scheduler.clone(),
+            events: events.into(),
+            origin,
+            hot: origin.is_some(),
+            delay: None,
+            completed: false,
+        }
+    }
+
+    /// Poll the stream until it completes, or until nothing can happen anymore,
+    /// and record every event with the frame it happened at.
+    pub fn record<S: Stream>(&self, s: S) -> Recorded<S::Item> {
+        let mut s = Box::pin(s);
+        let mut events = Vec::new();
+        let subscribed = self.scheduler.now();
+        let scheduler = &self.scheduler;
+        let mut f = futures::future::poll_fn(|cx| {
+            loop {
+                let item = ready!(s.as_mut().poll_next(cx));
+                let frame = (scheduler.now() - subscribed).as_millis() as u64;
+                match item {
+                    Some(v) => events.push((frame, Notification::Next(v))),
+                    None => {
+                        events.push((frame, Notification::Complete));
+                        return Poll::Ready(())
+                    }
+                }
+            }
+        });
+        let _ = self.scheduler.run_until_stalled(&mut f);
+        events
+    }
+
+    /// Assert the stream produces exactly what the marble describes.
+    pub fn expect<S: Stream>(&self, s: S, marble: &str, values: &[(char, S::Item)])
+        where S::Item: Clone + PartialEq + Debug
+    {
+        assert_eq!(self.record(s), parse_marble(marble, values), "marble {:?}", marble);
+    }
+
+    /// Assert the fallible stream produces exactly what the marble describes.
+    pub fn expect_result<S, T, E>(&self, s: S, marble: &str, values: &[(char, T)], error: E)
+        where
+            S: Stream<Item = Result<T, E>>,
+            T: Clone + PartialEq + Debug,
+            E: Clone + PartialEq + Debug,
+    {
+        assert_eq!(self.record(s), parse_marble_result(marble, values, error), "marble {:?}", marble);
+    }
+}
+
+impl Default for TestScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scheduler for TestScheduler {
+    type Delay = VirtualDelay;
+
+    fn now(&self) -> Instant {
+        self.scheduler.now()
+    }
+
+    fn delay_until(&self, deadline: Instant) -> Self::Delay {
+        self.scheduler.delay_until(deadline)
+    }
+}
+
+/// Stream replaying the events of a marble on the virtual time.
+#[derive(Debug)]
+#[must_use = "streams do nothing unless polled"]
+pub struct TestStream<T> {
+    scheduler: VirtualTimeScheduler,
+    events: VecDeque<(u64, Notification<T>)>,
+    origin: Option<Instant>,
+    hot: bool,
+    delay: Option<VirtualDelay>,
+    completed: bool,
+}
+
+// Values are never pinned, they are moved out as soon as they are due
+impl<T> Unpin for TestStream<T> {}
+
+impl<T> Stream for TestStream<T> {
+    type Item = T;
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        if this.completed {
+            return Poll::Ready(None)
+        }
+        let now = this.scheduler.now();
+        let origin = *this.origin.get_or_insert(now);
+        if this.hot {
+            // A hot stream does not replay what happened before the first poll
+            this.hot = false;
+            while let Some((frame, _)) = this.events.front() {
+                if origin + Duration::from_millis(*frame) >= now {
+                    break
+                }
+                this.events.pop_front();
+            }
+        }
+        let deadline = match this.events.front() {
+            Some((frame, _)) => origin + Duration::from_millis(*frame),
+            // Nothing left but no completion either, wait forever
+            None => return Poll::Pending,
+        };
+        if now < deadline {
+            let scheduler = &this.scheduler;
+            let delay = this.delay.get_or_insert_with(|| scheduler.delay_until(deadline));
+            ready!(Pin::new(delay).poll(cx));
+        }
+        this.delay = None;
+        match this.events.pop_front() {
+            Some((_, Notification::Next(v))) => Poll::Ready(Some(v)),
+            _ => {
+                this.completed = true;
+                Poll::Ready(None)
+            }
+        }
+    }
+}
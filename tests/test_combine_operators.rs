@@ -1,15 +1,32 @@
 use rxstream::source;
 use rxstream::operators::*;
 use rxstream::operators::RxStreamEx;
-use futures::StreamExt;
+use futures::{FutureExt, StreamExt};
 use tokio::{time::timeout};
 use std::time::{Duration};
+use std::task::Poll;
+use rxstream::testing::TestScheduler;
+use rxstream::scheduler::VirtualTimeScheduler;
 
 #[tokio::test]
 async fn combine_latest_combines_two() {
     let t1 = source::interval_immediate(10).take(3);
     let t2 = source::timer(3, 10).take(4);
     let r = combine_latest(t1, t2).collect::<Vec<_>>().await;
+    // Ticks running late under load are combined at once, the exact steps
+    // are checked on virtual time
+    assert_eq!(r.first(), Some(&(0, 0)));
+    assert_eq!(r.last(), Some(&(2, 3)));
+    assert!(r.windows(2).all(|w| w[0] != w[1] && w[0].0 <= w[1].0 && w[0].1 <= w[1].1), "{:?}", r);
+}
+
+// Timers 3ms apart race the wall clock under load, virtual time can't
+#[test]
+fn combine_latest_combines_two_on_virtual_time() {
+    let scheduler = VirtualTimeScheduler::new();
+    let t1 = source::interval_immediate_with_scheduler(10, scheduler.clone()).take(3);
+    let t2 = source::timer_with_scheduler(3, 10, scheduler.clone()).take(4);
+    let r = scheduler.run(combine_latest(t1, t2).collect::<Vec<_>>());
     assert_eq!(r, vec![(0, 0), (1, 0), (1, 1), (2, 1), (2, 2), (2, 3)])
 }
 
@@ -26,14 +43,39 @@ async fn combine_all_combines_all_streams_from_stream() {
     let r = combine_all(
         source::of(0..3).map(|i| source::timer(i*3, 10).take(3))
     ).collect::<Vec<_>>().await;
-    assert_eq!(r, [[0,0,0], [1,0,0], [1,1,0], [1,1,1], [2,1,1], [2,2,1], [2,2,2]])
+    // Ticks firing late together are combined at once, the exact steps are
+    // checked on virtual time
+    assert!(r[0].iter().all(|v| *v == 0), "{:?}", r);
+    assert_eq!(r.last(), Some(&vec![2, 2, 2]));
+    assert!(r.windows(2).all(|w| w[0] != w[1] && w[0].iter().zip(&w[1]).all(|(a, b)| a <= b)));
+}
+
+#[test]
+fn combine_all_combines_all_streams_on_virtual_time() {
+    let scheduler = VirtualTimeScheduler::new();
+    let r = scheduler.run(combine_all(
+        source::of(0..3).map(|i| source::timer_with_scheduler(i*3, 10, scheduler.clone()).take(3))
+    ).collect::<Vec<_>>());
+    // The first inner yields before the next ones come
+    assert_eq!(r, [vec![0], vec![0,0,0], vec![1,0,0], vec![1,1,0], vec![1,1,1], vec![2,1,1], vec![2,2,1], vec![2,2,2]])
 }
 
 #[tokio::test]
 async fn merge_merge_two() {
     let t1 = source::interval(10).take(3);
     let t2 = source::timer(3, 10).take(4);
-    let r = merge(t1, t2).collect::<Vec<_>>().await;
+    let mut r = merge(t1, t2).collect::<Vec<_>>().await;
+    // Late ticks may swap, the exact order is checked on virtual time
+    r.sort_unstable();
+    assert_eq!(r, vec![0, 0, 1, 1, 2, 2, 3])
+}
+
+#[test]
+fn merge_merge_two_on_virtual_time() {
+    let scheduler = VirtualTimeScheduler::new();
+    let t1 = source::interval_with_scheduler(10, scheduler.clone()).take(3);
+    let t2 = source::timer_with_scheduler(3, 10, scheduler.clone()).take(4);
+    let r = scheduler.run(merge(t1, t2).collect::<Vec<_>>());
     assert_eq!(r, vec![0, 0, 1, 1, 2, 2, 3])
 }
 
@@ -82,24 +124,40 @@ async fn test_concat_all_concats_all() {
 #[tokio::test]
 async fn test_race_pick_first_respond_item() {
     fn m2(i: u64) -> u64 {
-        return i * 2;
+        i * 2
     }
     fn m21(i: u64) -> u64 {
-        return i * 2 + 1;
+        i * 2 + 1
     }
     let t1 = source::timer(3, 10).map(m2 as fn(u64) -> u64).take(3);
     let t2 = source::timer(1, 10).map(m21 as fn(u64) -> u64).take(6);
     let r = race(t1, t2).collect::<Vec<_>>().await;
+    // Polled 2ms late both have fired and the tie goes to the first
+    assert!(r == [1,3,5,7,9,11] || r == [0,2,4], "{:?}", r)
+}
+
+#[test]
+fn test_race_pick_first_respond_item_on_virtual_time() {
+    fn m2(i: u64) -> u64 {
+        i * 2
+    }
+    fn m21(i: u64) -> u64 {
+        i * 2 + 1
+    }
+    let scheduler = VirtualTimeScheduler::new();
+    let t1 = source::timer_with_scheduler(3, 10, scheduler.clone()).map(m2 as fn(u64) -> u64).take(3);
+    let t2 = source::timer_with_scheduler(1, 10, scheduler.clone()).map(m21 as fn(u64) -> u64).take(6);
+    let r = scheduler.run(race(t1, t2).collect::<Vec<_>>());
     assert_eq!(r, [1,3,5,7,9,11])
 }
 
 #[tokio::test]
 async fn test_race_pick_first_ended() {
     fn m2(i: u64) -> u64 {
-        return i * 2;
+        i * 2
     }
     fn m21(i: u64) -> u64 {
-        return i * 2 + 1;
+        i * 2 + 1
     }
     let t1 = source::timer(3, 10).map(m2 as fn(u64) -> u64).take(3);
     let t2 = source::timer(1, 10).map(m21 as fn(u64) -> u64).take(0);
@@ -112,5 +170,354 @@ async fn test_with_latest_from_sync_the_stream() {
     let s1 = source::interval(5).take(3);
     let s2 = source::interval(1).take(100);
     let r = s1.with_latest_from(s2).collect::<Vec<_>>().await;
+    // The 1ms ticks run late under load, the exact pairs are checked on
+    // virtual time
+    assert_eq!(r.iter().map(|p| p.0).collect::<Vec<_>>(), [0, 1, 2]);
+    assert!(r.windows(2).all(|w| w[0].1 < w[1].1), "{:?}", r);
+    assert!(r.iter().all(|(v1, v2)| *v2 <= 5 * v1 + 5), "{:?}", r);
+}
+
+#[test]
+fn test_with_latest_from_sync_the_stream_on_virtual_time() {
+    // A 1ms interval races the wall clock, virtual time can't
+    let scheduler = VirtualTimeScheduler::new();
+    let s1 = source::interval_with_scheduler(5, scheduler.clone()).take(3);
+    let s2 = source::interval_with_scheduler(1, scheduler.clone()).take(100);
+    let r = scheduler.run(s1.with_latest_from(s2).collect::<Vec<_>>());
     assert_eq!(r, [(0, 4), (1, 9), (2, 14)])
 }
+
+#[tokio::test]
+async fn with_latest_from2_always_ready_others_do_not_block() {
+    let s = source::of(vec![1, 2]).with_latest_from2(futures::stream::repeat(0), futures::stream::repeat('x'));
+    assert_eq!(s.collect::<Vec<_>>().await, vec![(1, 0, 'x'), (2, 0, 'x')]);
+}
+
+#[test]
+fn with_latest_from_always_ready_other_does_not_block() {
+    let s = source::of(vec![1, 2]).with_latest_from(futures::stream::repeat(0));
+    assert_eq!(s.collect::<Vec<_>>().now_or_never(), Some(vec![(1, 0), (2, 0)]));
+}
+
+#[test]
+fn combine_latest3_yields_flat_tuples() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold("-a---b-|", &[('a', 1), ('b', 2)]);
+    let s2 = ts.cold("--x----|", &[('x', 'x')]);
+    let s3 = ts.cold("---uv--|", &[('u', "u"), ('v', "v")]);
+    ts.expect(
+        combine_latest3(s1, s2, s3),
+        "---pqr-|",
+        &[('p', (1, 'x', "u")), ('q', (1, 'x', "v")), ('r', (2, 'x', "v"))],
+    );
+}
+
+#[test]
+fn combine_latest3_ends_when_any_ends_empty() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold("-a---b-|", &[('a', 1), ('b', 2)]);
+    let s2 = ts.cold("--|", &[('x', 'x')]);
+    let s3 = ts.cold("---u-v-|", &[('u', "u"), ('v', "v")]);
+    ts.expect(combine_latest3(s1, s2, s3), "--|", &[]);
+}
+
+#[test]
+fn fork_join3_joins_last_values() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold("-a-b|", &[('a', 1), ('b', 2)]);
+    let s2 = ts.cold("--x|", &[('x', 'x')]);
+    let s3 = ts.cold("u----v|", &[('u', "u"), ('v', "v")]);
+    ts.expect(fork_join3(s1, s2, s3), "------(p|)", &[('p', (2, 'x', "v"))]);
+}
+
+#[test]
+fn fork_join3_ends_as_soon_as_one_is_empty() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold("-a-b|", &[('a', 1), ('b', 2)]);
+    let s2 = ts.cold("--|", &[('x', 'x')]);
+    let s3 = ts.cold("u----v|", &[('u', "u"), ('v', "v")]);
+    ts.expect(fork_join3(s1, s2, s3), "--|", &[]);
+}
+
+#[tokio::test]
+async fn fork_join12_joins_twelve() {
+    let r = fork_join12(
+        source::of(0..1), source::of(0..2), source::of(0..3), source::of(0..4),
+        source::of(vec!['a']), source::of(vec!["b"]), source::of(vec![1.5]), source::of(vec![true]),
+        source::of(0..9), source::of(0..10), source::of(0..11), source::of(0..12),
+    ).collect::<Vec<_>>().await;
+    assert_eq!(r, vec![(0, 1, 2, 3, 'a', "b", 1.5, true, 8, 9, 10, 11)])
+}
+
+#[test]
+fn merge3_merges_different_stream_types() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3), ('d', 4)];
+    let s1 = ts.cold("-a---|", &v);
+    let s2 = source::of(vec![5]);
+    let s3 = ts.cold("---c-d--|", &v);
+    ts.expect(merge3(s1, s2, s3), "ea-c-d--|", &[('a', 1), ('c', 3), ('d', 4), ('e', 5)]);
+}
+
+#[test]
+fn race3_picks_first_respond() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3), ('d', 4)];
+    let s1 = ts.cold("---a|", &v);
+    let s2 = ts.cold("--b-|", &v);
+    let s3 = source::of(vec![5]).chain(ts.cold("-c|", &v)).skip(1);
+    ts.expect(race3(s1, s2, s3), "-c|", &v);
+}
+
+#[test]
+fn race_accepts_streams_of_different_types() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 30)];
+    let s1 = ts.cold("---a|", &v);
+    let s2 = ts.cold("--c-|", &[('c', 3)]).map(|i| i * 10);
+    ts.expect(race(s1, s2), "--c-|", &v);
+}
+
+#[test]
+fn race_drops_the_losers_once_picked() {
+    let alive = std::rc::Rc::new(());
+    let held = alive.clone();
+    let loser = source::never::<i32>().map(move |i| {
+        let _ = &held;
+        i
+    });
+    let mut r = race(source::of(vec![1]), loser);
+    assert_eq!(std::rc::Rc::strong_count(&alive), 2);
+    assert_eq!(r.next().now_or_never(), Some(Some(1)));
+    assert_eq!(std::rc::Rc::strong_count(&alive), 1);
+}
+
+#[test]
+fn race_ties_are_not_always_won_by_the_first() {
+    // Pending on the first poll, then always ready
+    fn ready_on_second_poll(v: i32) -> impl futures::Stream<Item = i32> {
+        let mut polled = false;
+        futures::stream::poll_fn(move |cx| {
+            if polled {
+                return Poll::Ready(Some(v))
+            }
+            polled = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        })
+    }
+    // The first poll starts from the first stream, the second from the next
+    let r = race(source::of(vec![1]), source::of(vec![2])).next().now_or_never();
+    assert_eq!(r, Some(Some(1)));
+    let r = futures::executor::block_on(race(ready_on_second_poll(1), ready_on_second_poll(2)).next());
+    assert_eq!(r, Some(2));
+    let r = futures::executor::block_on(race3(
+        ready_on_second_poll(1), ready_on_second_poll(2), ready_on_second_poll(3)
+    ).next());
+    assert_eq!(r, Some(2));
+}
+
+#[test]
+fn race_vec_picks_first_respond() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let s = vec![ts.cold("---a|", &v), ts.cold("--b--c|", &v), ts.cold("----|", &v)];
+    ts.expect(race_vec(s), "--b--c|", &v);
+}
+
+#[test]
+fn race_vec_of_no_stream_ends() {
+    let ts = TestScheduler::new();
+    ts.expect(race_vec(Vec::<futures::stream::Empty<i32>>::new()), "|", &[]);
+}
+
+#[test]
+fn race_all_races_inner_streams_as_they_come() {
+    let ts = TestScheduler::new();
+    let v = [('x', 1), ('y', 2)];
+    let inner = ts.clone();
+    let outer = ts.cold("a-b----|", &[('a', 0), ('b', 1)]).map(move |i| match i {
+        0 => inner.cold("----x|", &v),
+        _ => inner.cold("-y-|", &v),
+    });
+    ts.expect(race_all(outer), "---y-|", &v);
+}
+
+#[test]
+fn with_latest_from2_yields_flat_tuples() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a--b--c|", &[('a', 1), ('b', 2), ('c', 3)]);
+    let o1 = ts.cold("x-----y|", &[('x', 'x'), ('y', 'y')]);
+    let o2 = ts.cold("---u|", &[('u', "u")]);
+    ts.expect(
+        s.with_latest_from2(o1, o2),
+        "----p--q|",
+        &[('p', (2, 'x', "u")), ('q', (3, 'y', "u"))],
+    );
+}
+
+#[test]
+fn concat_all_does_not_wait_for_outer_end() {
+    let ts = TestScheduler::new();
+    let v = [('x', 1), ('y', 2)];
+    // The outer stream never ends
+    let s = concat_all(ts.cold("ab---", &[('a', "-x|"), ('b', "-y|")]).map(|m| ts.cold(m, &v)));
+    ts.expect(s, "-x-y", &v);
+}
+
+#[test]
+fn concat_all_takes_from_infinite_outer() {
+    let scheduler = VirtualTimeScheduler::new();
+    let r = scheduler.run(
+        concat_all(source::interval_with_scheduler(10, scheduler.clone()).map(|i| source::of(vec![i, i])))
+            .take(4)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(r, vec![0, 0, 1, 1]);
+}
+
+#[test]
+fn combine_all_widens_as_inners_come() {
+    let ts = TestScheduler::new();
+    let s = combine_all(
+        ts.cold("a--b|", &[('a', "-x-y|"), ('b', "-z|")])
+            .map(|m| ts.cold(m, &[('x', 1), ('y', 2), ('z', 3)]))
+    );
+    ts.expect(s, "-p--q|", &[('p', vec![1]), ('q', vec![2, 3])]);
+}
+
+#[test]
+fn combine_all_takes_from_infinite_outer() {
+    let s = combine_all(futures::stream::repeat(0).map(|_| futures::stream::iter(vec![1])));
+    assert_eq!(s.take(3).collect::<Vec<_>>().now_or_never(), Some(vec![vec![1], vec![1, 1], vec![1, 1, 1]]));
+}
+
+#[test]
+fn combine_all_ends_when_inner_ends_empty() {
+    let ts = TestScheduler::new();
+    let s = combine_all(
+        ts.cold("a-b----", &[('a', "-x-y|"), ('b', "-|")])
+            .map(|m| ts.cold(m, &[('x', 1), ('y', 2)]))
+    );
+    ts.expect(s, "-p-|", &[('p', vec![1])]);
+}
+
+#[test]
+fn merge_vec_merges_all() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    ts.expect(merge_vec(vec![ts.cold("-a-|", &v), ts.cold("--b|", &v), ts.cold("c|", &v)]), "cab|", &v);
+}
+
+#[tokio::test]
+async fn merge_vec_polls_fairly() {
+    let s = merge_vec(vec![source::repeat_value(0), source::repeat_value(1), source::repeat_value(2)]);
+    let r = s.take(30).collect::<Vec<_>>().await;
+    for i in 0..3 {
+        assert_eq!(r.iter().filter(|v| **v == i).count(), 10, "{:?}", r);
+    }
+}
+
+#[tokio::test]
+async fn merge_vec_merges_hundreds() {
+    let s = merge_vec((0..300).map(|i| source::of(vec![i])).collect());
+    assert_eq!(s.fold(0, |sum, i| async move { sum + i }).await, (0..300).sum::<i32>());
+}
+
+#[test]
+fn merge_vec_with_concurrency_starts_next_when_one_ends() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let s = merge_vec_with_concurrency(vec![ts.cold("-a|", &v), ts.cold("--b|", &v), ts.cold("-c|", &v)], 2);
+    ts.expect(s, "-abc|", &v);
+}
+
+#[test]
+fn merge_all_merges_inners_as_they_come() {
+    let ts = TestScheduler::new();
+    let v = [('x', 1), ('y', 2)];
+    let s = merge_all(ts.cold("a-b|", &[('a', "--x|"), ('b', "-y|")]).map(|m| ts.cold(m, &v)));
+    ts.expect(s, "--xy|", &v);
+}
+
+#[test]
+fn merge_all_with_concurrency_queues_inners() {
+    let ts = TestScheduler::new();
+    let v = [('x', 1), ('y', 2)];
+    let s = merge_all_with_concurrency(ts.cold("a-b|", &[('a', "--x|"), ('b', "-y|")]).map(|m| ts.cold(m, &v)), 1);
+    ts.expect(s, "--x-y|", &v);
+}
+
+#[tokio::test]
+async fn zip_vec_ends_with_shortest() {
+    let r = zip_vec(vec![source::of(0..3), source::of(10..15), source::of(20..22)])
+        .collect::<Vec<_>>().await;
+    assert_eq!(r, vec![vec![0, 10, 20], vec![1, 11, 21]])
+}
+
+#[tokio::test]
+async fn zip_vec_pad_with_none_ends_with_longest() {
+    let r = zip_vec_with_policy(vec![source::of(0..2), source::of(10..13), source::of(20..21)], PadWithNone)
+        .collect::<Vec<_>>().await;
+    assert_eq!(r, vec![
+        vec![Some(0), Some(10), Some(20)],
+        vec![Some(1), Some(11), None],
+        vec![None, Some(12), None],
+    ])
+}
+
+#[test]
+fn zip_vec_waits_for_each_nth_value() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('x', 3), ('y', 4), ('z', 5)];
+    let s = zip_vec(vec![ts.cold("-a--b|", &v), ts.cold("--x-y-z|", &v)]);
+    ts.expect(s, "--p-q|", &[('p', vec![1, 3]), ('q', vec![2, 4])]);
+}
+
+#[test]
+fn zip_all_zips_once_outer_ends() {
+    let ts = TestScheduler::new();
+    let v = [('x', 1), ('y', 2), ('u', 3), ('w', 4)];
+    let s = zip_all(ts.cold("a-b-|", &[('a', "x-y|"), ('b', "-u-w|")]).map(|m| ts.cold(m, &v)));
+    ts.expect(s, "----p(q|)", &[('p', vec![1, 3]), ('q', vec![2, 4])]);
+}
+
+#[tokio::test]
+async fn zip_zips_two() {
+    let r = zip(source::of(0..3), source::of(vec!['a', 'b'])).collect::<Vec<_>>().await;
+    assert_eq!(r, vec![(0, 'a'), (1, 'b')])
+}
+
+#[test]
+fn zip_all_returns_with_an_infinite_outer() {
+    let mut s = zip_all(futures::stream::repeat(0).map(|_| source::of(0..1)));
+    assert_eq!(s.next().now_or_never(), None);
+}
+
+#[test]
+fn zip3_yields_flat_tuples_of_nth_values() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold("-a--b--c|", &[('a', 1), ('b', 2), ('c', 3)]);
+    let s2 = ts.cold("--x-y|", &[('x', 'x'), ('y', 'y')]);
+    let s3 = ts.cold("---uvw|", &[('u', "u"), ('v', "v"), ('w', "w")]);
+    ts.expect(zip3(s1, s2, s3), "---pq|", &[('p', (1, 'x', "u")), ('q', (2, 'y', "v"))]);
+}
+
+#[tokio::test]
+async fn zip12_zips_twelve() {
+    let r = zip12(
+        source::of(0..2), source::of(vec!['a', 'b', 'c']), source::of(0..5), source::of(0..5),
+        source::of(0..5), source::of(0..5), source::of(0..5), source::of(0..5),
+        source::of(0..5), source::of(0..5), source::of(0..5), source::of(vec!["x", "y"]),
+    ).collect::<Vec<_>>().await;
+    assert_eq!(r, vec![
+        (0, 'a', 0, 0, 0, 0, 0, 0, 0, 0, 0, "x"),
+        (1, 'b', 1, 1, 1, 1, 1, 1, 1, 1, 1, "y"),
+    ])
+}
+
+#[test]
+fn race_all_returns_with_an_infinite_outer() {
+    let mut s = race_all(futures::stream::repeat(0).map(|_| futures::stream::pending::<i32>()));
+    assert_eq!(s.next().now_or_never(), None);
+}
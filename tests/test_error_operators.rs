@@ -0,0 +1,227 @@
+use rxstream::operators::{on_error_resume_next, Backoff, RxTryStreamEx};
+use rxstream::testing::{Notification, TestScheduler};
+use futures::future::ready;
+use futures::TryStreamExt;
+
+#[test]
+fn try_combine_latest_combines_oks() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold_result("-a--b|", &[('a', 1), ('b', 2)], "boom");
+    let s2 = ts.cold_result("--x-|", &[('x', 'x')], "boom");
+    ts.expect_result(
+        s1.try_combine_latest(s2),
+        "--p-q|",
+        &[('p', (1, 'x')), ('q', (2, 'x'))],
+        "boom",
+    );
+}
+
+#[test]
+fn try_combine_latest_ends_with_first_error() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold_result("-a--#", &[('a', 1)], "boom");
+    let s2 = ts.cold_result("--x---y|", &[('x', 'x'), ('y', 'y')], "boom");
+    ts.expect_result(s1.try_combine_latest(s2), "--p-#", &[('p', (1, 'x'))], "boom");
+}
+
+#[test]
+fn try_combine_latest_error_wins_over_value_at_same_frame() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold_result("-a-b|", &[('a', 1), ('b', 2)], "boom");
+    let s2 = ts.cold_result("-x-#", &[('x', 'x')], "boom");
+    ts.expect_result(s1.try_combine_latest(s2), "-p-#", &[('p', (1, 'x'))], "boom");
+}
+
+#[test]
+fn try_fork_join_errors_if_either_errors() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold_result("-a-b|", &[('a', 1), ('b', 2)], "boom");
+    let s2 = ts.cold_result("-x#", &[('x', 'x')], "boom");
+    ts.expect_result(s1.try_fork_join(s2), "--#", &[], "boom");
+}
+
+#[test]
+fn try_fork_join_joins_last_oks() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold_result("-a-b|", &[('a', 1), ('b', 2)], "boom");
+    let s2 = ts.cold_result("--x|", &[('x', 'x')], "boom");
+    ts.expect_result(s1.try_fork_join(s2), "----(p|)", &[('p', (2, 'x'))], "boom");
+}
+
+#[test]
+fn try_with_latest_from_errors_on_other_error() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold_result("--a--b|", &[('a', 1), ('b', 2)], "boom");
+    let s2 = ts.cold_result("-x-#", &[('x', 'x')], "boom");
+    ts.expect_result(s1.try_with_latest_from(s2), "--p#", &[('p', (1, 'x'))], "boom");
+}
+
+#[test]
+fn try_race_error_wins_when_first() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1)];
+    ts.expect_result(ts.cold_result("--#", &v, "boom").try_race(ts.cold_result("---a|", &v, "boom")), "--#", &v, "boom");
+}
+
+#[test]
+fn try_race_ignores_error_of_loser() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    ts.expect_result(ts.cold_result("-a-b|", &v, "boom").try_race(ts.cold_result("--#", &v, "boom")), "-a-b|", &v, "boom");
+}
+
+#[test]
+fn try_race_mixes_stream_types() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    let other = ts.cold_result("--a|", &v, "boom").map_ok(|x| x * 10);
+    ts.expect_result(ts.cold_result("-a-b|", &v, "boom").try_race(other), "-a-b|", &v, "boom");
+}
+
+#[test]
+fn try_pairwise_ends_with_error() {
+    let ts = TestScheduler::new();
+    let s = ts.cold_result("-a-b-c-#", &[('a', 1), ('b', 2), ('c', 3)], "boom").try_pairwise();
+    ts.expect_result(s, "---x-y-#", &[('x', (1, 2)), ('y', (2, 3))], "boom");
+}
+
+#[test]
+fn try_buffer_count_drops_partial_buffer_on_error() {
+    let ts = TestScheduler::new();
+    let s = ts.cold_result("-a-b-c#", &[('a', 1), ('b', 2), ('c', 3)], "boom").try_buffer_count(2);
+    ts.expect_result(s, "---x--#", &[('x', vec![1, 2])], "boom");
+}
+
+#[test]
+fn try_buffer_count_with_skip_ends_with_error() {
+    let ts = TestScheduler::new();
+    let s = ts.cold_result("abc#", &[('a', 1), ('b', 2), ('c', 3)], "boom").try_buffer_count_with_skip(2, 1);
+    ts.expect_result(s, "-xy#", &[('x', vec![1, 2]), ('y', vec![2, 3])], "boom");
+}
+
+#[test]
+fn try_buffer_time_ends_with_error() {
+    let ts = TestScheduler::new();
+    let s = ts.cold_result("-a-b-#", &[('a', 1), ('b', 2)], "boom")
+        .try_buffer_time_with_scheduler(3, ts.clone());
+    ts.expect_result(s, "---x-#", &[('x', vec![1])], "boom");
+}
+
+#[test]
+fn retry_recreates_stream_until_count() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    let s = ts.cold_result("-a#", &v, "boom").retry(2, || ts.cold_result("-b#", &v, "boom"));
+    ts.expect_result(s, "-a-b-b#", &v, "boom");
+}
+
+#[test]
+fn retry_ends_when_recreated_stream_ends() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    let s = ts.cold_result("-a#", &v, "boom").retry(3, || ts.cold_result("-b|", &v, "boom"));
+    ts.expect_result(s, "-a-b|", &v, "boom");
+}
+
+#[test]
+fn retry_with_backoff_waits_exponentially_up_to_max() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    let s = ts.cold_result("-a#", &v, "boom").retry_with_backoff_and_scheduler(
+        Backoff::new(2, 10, 15),
+        || ts.cold_result("b#", &v, "boom"),
+        ts.clone(),
+    );
+    // Retries after 10ms, then 15ms as 20ms is over the max
+    assert_eq!(ts.record(s), vec![
+        (1, Notification::Next(Ok(1))),
+        (12, Notification::Next(Ok(2))),
+        (28, Notification::Next(Ok(2))),
+        (29, Notification::Next(Err("boom"))),
+        (29, Notification::Complete),
+    ])
+}
+
+#[test]
+fn retry_with_backoff_jitter_shortens_delay() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1)];
+    let s = ts.cold_result("#", &v, "boom").retry_with_backoff_and_scheduler(
+        Backoff::new(1, 100, 100).with_jitter(0.5),
+        || ts.cold_result("a|", &v, "boom"),
+        ts.clone(),
+    );
+    let r = ts.record(s);
+    assert_eq!(r[0].1, Notification::Next(Ok(1)));
+    assert!((50..=100).contains(&r[0].0), "retried at {}", r[0].0);
+}
+
+#[test]
+fn backoff_delay_doubles_and_caps() {
+    let b = Backoff::new(10, 10, 100);
+    assert_eq!((0..6).map(|n| b.delay(n)).collect::<Vec<_>>(), vec![10, 20, 40, 80, 100, 100]);
+    assert_eq!(b.delay(200), 100);
+}
+
+#[test]
+fn retry_when_notifier_decides() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    let s = ts.cold_result("-a#", &v, "flaky").retry_when(
+        || ts.cold_result("-b#", &v, "fatal"),
+        |e| ready(if e == "fatal" { Err(e) } else { Ok(()) }),
+    );
+    ts.expect_result(s, "-a-b#", &v, "fatal");
+}
+
+#[test]
+fn catch_error_switches_to_replacement() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let s = ts.cold_result("-a#", &v, "boom")
+        .catch_error(|e| {
+            assert_eq!(e, "boom");
+            ts.cold_result("-b-c|", &v, "boom")
+        });
+    ts.expect_result(s, "-a-b-c|", &v, "boom");
+}
+
+#[test]
+fn catch_error_yields_errors_of_replacement() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    let s = ts.cold_result("-a#", &v, "boom")
+        .catch_error(|_| ts.cold_result("-b#", &v, 42));
+    ts.expect_result(s, "-a-b#", &v, 42);
+}
+
+#[test]
+fn catch_error_passes_through_without_error() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1)];
+    let s = ts.cold_result("-a|", &v, "boom")
+        .catch_error(|_| ts.cold_result("-a|", &v, "boom"));
+    ts.expect_result(s, "-a|", &v, "boom");
+}
+
+#[test]
+fn on_error_resume_next_moves_on_after_error_or_end() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let s = on_error_resume_next(vec![
+        ts.cold_result("-a#", &v, "boom"),
+        ts.cold_result("-b|", &v, "boom"),
+        ts.cold_result("#", &v, "boom"),
+        ts.cold_result("-c#", &v, "boom"),
+    ]);
+    ts.expect(s, "-a-b-c|", &v);
+}
+
+#[test]
+fn on_error_resume_next_after_this_stream() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    let s = ts.cold_result("-a#", &v, "boom")
+        .on_error_resume_next(vec![ts.cold_result("-b|", &v, "boom")]);
+    ts.expect(s, "-a-b|", &v);
+}
@@ -0,0 +1,264 @@
+use rxstream::operators::{RxStreamEx, SequenceError, ThrottleConfig};
+use rxstream::testing::{TestScheduler, TestStream};
+use futures::{stream, FutureExt, Stream};
+
+#[test]
+fn debounce_time_yields_after_silence() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let s = ts.cold("-a-b----c---|", &v).debounce_time_with_scheduler(3, ts.clone());
+    ts.expect(s, "------b----c|", &v);
+}
+
+#[test]
+fn debounce_time_flushes_on_end() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    let s = ts.cold("-a-b|", &v).debounce_time_with_scheduler(5, ts.clone());
+    ts.expect(s, "----(b|)", &v);
+}
+
+#[test]
+fn throttle_time_yields_leading_values() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3), ('d', 4)];
+    let s = ts.cold("-a-b-c-d---|", &v)
+        .throttle_time_with_config_and_scheduler(3, ThrottleConfig::default(), ts.clone());
+    ts.expect(s, "-a---c-----|", &v);
+}
+
+#[test]
+fn throttle_time_yields_trailing_values() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let config = ThrottleConfig { leading: false, trailing: true };
+    let s = ts.cold("-a-b-----c|", &v).throttle_time_with_config_and_scheduler(3, config, ts.clone());
+    ts.expect(s, "----b-------(c|)", &v);
+}
+
+#[test]
+fn throttle_time_yields_leading_and_trailing_values() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let config = ThrottleConfig { leading: true, trailing: true };
+    let s = ts.cold("ab-c----|", &v).throttle_time_with_config_and_scheduler(4, config, ts.clone());
+    ts.expect(s, "a---c---|", &v);
+}
+
+#[test]
+fn audit_time_yields_last_value_of_window() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let s = ts.cold("-a-b----c-|", &v).audit_time_with_scheduler(3, ts.clone());
+    ts.expect(s, "----b------(c|)", &v);
+}
+
+#[test]
+fn sample_time_yields_last_value_of_period() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3), ('d', 4)];
+    let s = ts.cold("-a--b--c---d-|", &v).sample_time_with_scheduler(3, ts.clone());
+    ts.expect(s, "---a--b--c--d|", &v);
+}
+
+#[test]
+fn sample_time_drops_value_waiting_at_end() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1)];
+    let s = ts.cold("----a|", &v).sample_time_with_scheduler(3, ts.clone());
+    ts.expect(s, "-----|", &v);
+}
+
+// Duration stream of n frames, closing the window by yielding
+fn yield_after(ts: &TestScheduler) -> impl FnMut(&i32) -> TestStream<()> {
+    let ts = ts.clone();
+    move |n| ts.cold(&format!("{}x", "-".repeat(*n as usize)), &[('x', ())])
+}
+
+#[test]
+fn sample_yields_last_value_on_notifier() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let notifier = ts.cold("--x----x-x--", &[('x', ())]);
+    ts.expect(ts.cold("-a-b--c---|", &v).sample(notifier), "--a----c--|", &v);
+}
+
+#[test]
+fn debounce_closes_window_on_end_of_duration() {
+    let ts = TestScheduler::new();
+    let v = [('a', 4), ('b', 2)];
+    let inner = ts.clone();
+    let s = ts.cold("-a-b------|", &v)
+        .debounce(move |n: &i32| inner.cold::<()>(&format!("{}|", "-".repeat(*n as usize)), &[]));
+    ts.expect(s, "-----b----|", &v);
+}
+
+#[test]
+fn audit_yields_last_value_when_duration_yields() {
+    let ts = TestScheduler::new();
+    let v = [('a', 3), ('b', 5), ('c', 2)];
+    let s = ts.cold("-a-b----c-|", &v).audit(yield_after(&ts));
+    ts.expect(s, "----b-----(c|)", &v);
+}
+
+#[test]
+fn throttle_drops_values_until_duration_yields() {
+    let ts = TestScheduler::new();
+    let v = [('a', 3), ('b', 1), ('c', 1), ('d', 1)];
+    let s = ts.cold("-a-b-c-d---|", &v).throttle(yield_after(&ts));
+    ts.expect(s, "-a---c-d---|", &v);
+}
+
+fn next<S: Stream + Unpin>(s: &mut S) -> Option<Option<S::Item>> {
+    futures::future::poll_fn(|cx| std::pin::Pin::new(&mut *s).poll_next(cx)).now_or_never()
+}
+
+#[test]
+fn always_ready_source_does_not_block() {
+    let never = |_: &i32| stream::pending::<()>();
+    let mut s = stream::repeat(1).sample(stream::pending::<()>());
+    assert_eq!(next(&mut s), None);
+    let mut s = stream::repeat(1).debounce(never);
+    assert_eq!(next(&mut s), None);
+    let mut s = stream::repeat(1).audit(never);
+    assert_eq!(next(&mut s), None);
+    let mut s = stream::repeat(1).throttle(never);
+    assert_eq!(next(&mut s), Some(Some(1)));
+    assert_eq!(next(&mut s), None);
+}
+
+#[test]
+fn distinct_forgets_seen_values_on_flush() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    let flush = ts.cold("------x", &[('x', ())]);
+    let s = ts.cold("-a-b-a-b-a|", &v).distinct_with_flush(|v| *v, flush);
+    ts.expect(s, "-a-b---b-a|", &v);
+    ts.expect(ts.cold("-a-b-a-b-a|", &v).distinct(), "-a-b------|", &v);
+}
+
+#[test]
+fn distinct_with_an_always_ready_flush_returns() {
+    let mut s = Box::pin(stream::pending::<i32>().distinct_with_flush(|v| *v, stream::repeat(())));
+    assert_eq!(futures::StreamExt::next(&mut s).now_or_never(), None);
+    let s = stream::iter(vec![1, 1, 2]).distinct_with_flush(|v| *v, stream::repeat(()));
+    assert_eq!(futures::executor::block_on(futures::StreamExt::collect::<Vec<_>>(s)), vec![1, 1, 2]);
+}
+
+#[test]
+fn distinct_until_changed_drops_repeats() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    ts.expect(ts.cold("-a-a-b-b-a|", &v).distinct_until_changed(), "-a---b---a|", &v);
+}
+
+#[test]
+fn distinct_until_key_changed_compares_keys() {
+    let ts = TestScheduler::new();
+    let v = [('a', 11), ('b', 12), ('c', 21)];
+    ts.expect(ts.cold("-a-b-c|", &v).distinct_until_key_changed(|v| v / 10), "-a---c|", &v);
+}
+
+#[test]
+fn first_yields_first_value_or_empty_error() {
+    let ts = TestScheduler::new();
+    let v = [('a', Ok(1)), ('b', Ok(2)), ('e', Err(SequenceError::Empty))];
+    ts.expect(ts.cold("-a-b|", &[('a', 1), ('b', 2)]).first(), "-(a|)", &v);
+    ts.expect(ts.cold::<i32>("---|", &[]).first(), "---(e|)", &v);
+}
+
+#[test]
+fn first_with_yields_first_match_or_default() {
+    let ts = TestScheduler::new();
+    let values = [('a', 1), ('b', 2), ('c', 3)];
+    let v = [('b', Ok(2)), ('d', Ok(9)), ('e', Err(SequenceError::Empty))];
+    ts.expect(ts.cold("-a-b-c|", &values).first_with(|v| v % 2 == 0, None), "---(b|)", &v);
+    ts.expect(ts.cold("-a-c|", &values).first_with(|v| v % 2 == 0, Some(9)), "----(d|)", &v);
+    ts.expect(ts.cold("-a-c|", &values).first_with(|v| v % 2 == 0, None), "----(e|)", &v);
+}
+
+#[test]
+fn element_at_yields_value_at_index_or_out_of_range() {
+    let ts = TestScheduler::new();
+    let values = [('a', 1), ('b', 2), ('c', 3)];
+    let v = [('b', Ok(2)), ('e', Err(SequenceError::OutOfRange))];
+    ts.expect(ts.cold("-a-b-c|", &values).element_at(1), "---(b|)", &v);
+    ts.expect(ts.cold("-a-b-c|", &values).element_at(5), "------(e|)", &v);
+}
+
+#[test]
+fn last_yields_last_value_on_end() {
+    let ts = TestScheduler::new();
+    let v = [('b', Ok(2)), ('e', Err(SequenceError::Empty))];
+    ts.expect(ts.cold("-a-b|", &[('a', 1), ('b', 2)]).last(), "----(b|)", &v);
+    ts.expect(ts.cold::<i32>("--|", &[]).last(), "--(e|)", &v);
+}
+
+#[test]
+fn last_with_yields_last_match_or_default() {
+    let ts = TestScheduler::new();
+    let values = [('a', 1), ('b', 2), ('c', 3)];
+    let v = [('a', Ok(1)), ('d', Ok(9)), ('e', Err(SequenceError::Empty))];
+    ts.expect(ts.cold("-a-b-c|", &values).last_with(|v| v % 2 == 1, None), "------(c|)", &[('c', Ok(3))]);
+    ts.expect(ts.cold("-a-b|", &values).last_with(|v| v % 2 == 1, None), "----(a|)", &v);
+    ts.expect(ts.cold("-b|", &values).last_with(|v| v % 2 == 1, Some(9)), "--(d|)", &v);
+    ts.expect(ts.cold("-b|", &values).last_with(|v| v % 2 == 1, None), "--(e|)", &v);
+}
+
+#[test]
+fn single_errors_on_second_value() {
+    let ts = TestScheduler::new();
+    let values = [('a', 1), ('b', 2)];
+    let v = [('a', Ok(1)), ('t', Err(SequenceError::TooMany)), ('e', Err(SequenceError::Empty))];
+    ts.expect(ts.cold("-a--|", &values).single(), "----(a|)", &v);
+    ts.expect(ts.cold("-a-b-|", &values).single(), "---(t|)", &v);
+    ts.expect(ts.cold::<i32>("-|", &[]).single(), "-(e|)", &v);
+}
+
+#[test]
+fn take_until_ends_when_notifier_yields() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let stop = ts.cold("----x", &[('x', "stop")]);
+    ts.expect(ts.cold("-a-b-c|", &v).take_until(stop), "-a-b|", &v);
+}
+
+#[test]
+fn take_until_ignores_notifier_ending_without_value() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let stop = ts.cold::<()>("--|", &[]);
+    ts.expect(ts.cold("-a-b-c|", &v).take_until(stop), "-a-b-c|", &v);
+}
+
+#[test]
+fn skip_until_yields_after_notifier_yields() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let start = ts.cold("--x", &[('x', 0.5)]);
+    ts.expect(ts.cold("-a-b-c|", &v).skip_until(start), "---b-c|", &v);
+    let never = ts.cold::<()>("--|", &[]);
+    ts.expect(ts.cold("-a-b-c|", &v).skip_until(never), "------|", &v);
+}
+
+#[test]
+fn take_last_yields_last_values_on_end() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    ts.expect(ts.cold("-a-b-c|", &v).take_last(2), "------(bc|)", &v);
+    ts.expect(ts.cold("-a-b-c|", &v).take_last(0), "------|", &v);
+}
+
+#[test]
+fn skip_last_yields_values_count_late() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3), ('d', 4)];
+    ts.expect(ts.cold("-a-b-c-d|", &v).skip_last(2), "-----a-b|", &v);
+}
+
+#[test]
+fn ignore_elements_keeps_only_end() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    ts.expect(ts.cold("-a-b--|", &v).ignore_elements(), "------|", &v);
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "futures-timer")]
+use rxstream::source;
+use rxstream::operators::RxStreamEx;
+use rxstream::scheduler::FuturesTimerScheduler;
+use futures::executor::block_on;
+use futures::StreamExt;
+
+#[test]
+fn futures_timer_interval_runs_without_tokio() {
+    let r = block_on(
+        source::interval_with_scheduler(5, FuturesTimerScheduler).take(3).collect::<Vec<_>>()
+    );
+    assert_eq!(r, vec![0, 1, 2])
+}
+
+#[test]
+fn futures_timer_buffer_time_runs_without_tokio() {
+    let r = block_on(
+        source::interval_with_scheduler(31, FuturesTimerScheduler)
+            .buffer_time_with_scheduler(50, FuturesTimerScheduler)
+            .take(2)
+            .collect::<Vec<_>>()
+    );
+    // Ticks may land on either side of a buffer boundary, only the values
+    // and their order are certain
+    let values = r.iter().flatten().copied().collect::<Vec<_>>();
+    assert_eq!(values, (0..values.len() as u64).collect::<Vec<_>>());
+    assert!(r.iter().any(|b| !b.is_empty()));
+}
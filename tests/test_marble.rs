@@ -0,0 +1,135 @@
+use rxstream::operators::*;
+use rxstream::operators::RxStreamEx;
+use rxstream::testing::{parse_marble, Notification, TestScheduler};
+
+#[test]
+fn parse_marble_counts_group_and_frames() {
+    let r = parse_marble("-a-(bc)-|", &[('a', 1), ('b', 2), ('c', 3)]);
+    assert_eq!(r, vec![
+        (1, Notification::Next(1)),
+        (3, Notification::Next(2)),
+        (3, Notification::Next(3)),
+        (8, Notification::Complete),
+    ])
+}
+
+#[test]
+fn parse_marble_drops_events_before_subscription_point() {
+    let r = parse_marble("a-^-b-|", &[('a', 1), ('b', 2)]);
+    assert_eq!(r, vec![(2, Notification::Next(2)), (4, Notification::Complete)])
+}
+
+#[test]
+fn parse_marble_ignores_whitespace() {
+    assert_eq!(parse_marble("  -a |", &[('a', 1)]), parse_marble("-a|", &[('a', 1)]))
+}
+
+#[test]
+#[should_panic]
+fn parse_marble_rejects_error_on_infallible_stream() {
+    parse_marble("-a#", &[('a', 1)]);
+}
+
+#[test]
+fn cold_stream_replays_marble() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    ts.expect(ts.cold("-a--b|", &v), "-a--b|", &v);
+}
+
+#[test]
+fn cold_stream_without_completion_never_ends() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1)];
+    ts.expect(ts.cold("-a--", &v), "-a", &v);
+}
+
+#[test]
+fn hot_stream_misses_values_before_subscription() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    let s = concat(ts.cold("--|", &v), ts.hot("-a-b-|", &v));
+    ts.expect(s, "---b-|", &v);
+}
+
+#[test]
+fn cold_result_stream_ends_with_error() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1)];
+    ts.expect_result(ts.cold_result("-a-#", &v, "boom"), "-a-#", &v, "boom");
+}
+
+#[test]
+fn pairwise_marble() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b-c|", &[('a', 1), ('b', 2), ('c', 3)]).pairwise();
+    ts.expect(s, "---x-y|", &[('x', (1, 2)), ('y', (2, 3))]);
+}
+
+#[test]
+fn combine_latest_marble() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold("-a--b---|", &[('a', 1), ('b', 2)]);
+    let s2 = ts.cold("--x--y-|", &[('x', 'x'), ('y', 'y')]);
+    ts.expect(
+        combine_latest(s1, s2),
+        "--p-qr--|",
+        &[('p', (1, 'x')), ('q', (2, 'x')), ('r', (2, 'y'))],
+    );
+}
+
+#[test]
+fn with_latest_from_marble() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold("--a--b--c|", &[('a', 1), ('b', 2), ('c', 3)]);
+    let s2 = ts.cold("-x--y|", &[('x', 'x'), ('y', 'y')]);
+    ts.expect(
+        s1.with_latest_from(s2),
+        "--p--q--r|",
+        &[('p', (1, 'x')), ('q', (2, 'y')), ('r', (3, 'y'))],
+    );
+}
+
+#[test]
+fn merge_marble() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('x', 3)];
+    ts.expect(merge(ts.cold("-a--b|", &v), ts.cold("--x-|", &v)), "-ax-b|", &v);
+}
+
+#[test]
+fn concat_marble() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2)];
+    ts.expect(concat(ts.cold("-a|", &v), ts.cold("-b|", &v)), "-a-b|", &v);
+}
+
+#[test]
+fn race_marble() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('x', 3), ('y', 4)];
+    ts.expect(race(ts.cold("---a-b|", &v), ts.cold("--x--y|", &v)), "--x--y|", &v);
+}
+
+#[test]
+fn fork_join_marble() {
+    let ts = TestScheduler::new();
+    let s1 = ts.cold("-a-b|", &[('a', 1), ('b', 2)]);
+    let s2 = ts.cold("--x|", &[('x', 'x')]);
+    ts.expect(fork_join(s1, s2), "----(p|)", &[('p', (2, 'x'))]);
+}
+
+#[test]
+fn buffer_count_marble() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b-c|", &[('a', 1), ('b', 2), ('c', 3)]).buffer_count(2);
+    ts.expect(s, "---x--(y|)", &[('x', vec![1, 2]), ('y', vec![3])]);
+}
+
+#[test]
+fn buffer_time_marble() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b-c---|", &[('a', 1), ('b', 2), ('c', 3)])
+        .buffer_time_with_scheduler(3, ts.clone());
+    ts.expect(s, "---x--y--(z|)", &[('x', vec![1]), ('y', vec![2, 3]), ('z', vec![])]);
+}
@@ -0,0 +1,152 @@
+use rxstream::source;
+use rxstream::operators::*;
+use rxstream::operators::RxStreamEx;
+use rxstream::scheduler::VirtualTimeScheduler;
+use futures::{FutureExt, StreamExt};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[test]
+fn share_feeds_both_sides_in_lock_step() {
+    let scheduler = VirtualTimeScheduler::new();
+    let ticks = source::interval_with_scheduler(10, scheduler.clone()).take(3).share();
+    let r = scheduler.run(
+        combine_latest(ticks.clone(), ticks.map(|i| i * 10)).collect::<Vec<_>>()
+    );
+    assert_eq!(r, vec![(0, 0), (1, 10), (2, 20)]);
+}
+
+#[tokio::test]
+async fn share_late_handle_misses_earlier_values() {
+    let mut s1 = source::of(0..4).share();
+    assert_eq!(s1.next().await, Some(0));
+    let s2 = s1.clone();
+    assert_eq!(s1.collect::<Vec<_>>().await, vec![1, 2, 3]);
+    assert_eq!(s2.collect::<Vec<_>>().await, vec![1, 2, 3]);
+}
+
+#[tokio::test]
+async fn share_replay_late_handle_gets_last_values() {
+    let mut s1 = source::of(0..4).share_replay(2);
+    assert_eq!(s1.next().await, Some(0));
+    assert_eq!(s1.next().await, Some(1));
+    assert_eq!(s1.next().await, Some(2));
+    let s2 = s1.clone();
+    assert_eq!(s2.collect::<Vec<_>>().await, vec![1, 2, 3]);
+    assert_eq!(s1.collect::<Vec<_>>().await, vec![3]);
+}
+
+#[tokio::test]
+async fn share_handles_on_different_tasks() {
+    let ticks = source::interval(5).take(3).share();
+    let other = tokio::spawn(ticks.clone().collect::<Vec<_>>());
+    assert_eq!(ticks.collect::<Vec<_>>().await, vec![0, 1, 2]);
+    assert_eq!(other.await.unwrap(), vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn publish_waits_for_connect() {
+    let published = source::of(0..3).publish();
+    let mut s1 = published.subscribe();
+    let s2 = published.subscribe();
+    assert_eq!(s1.next().now_or_never(), None);
+    published.connect();
+    assert_eq!(s1.collect::<Vec<_>>().await, vec![0, 1, 2]);
+    assert_eq!(s2.collect::<Vec<_>>().await, vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn publish_wakes_handles_on_connect() {
+    let published = source::of(0..3).publish();
+    let handle = tokio::spawn(published.subscribe().collect::<Vec<_>>());
+    tokio::task::yield_now().await;
+    published.connect();
+    assert_eq!(handle.await.unwrap(), vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn publish_ref_count_connects_on_first_subscribe() {
+    let published = source::of(0..3).publish();
+    let mut early = published.subscribe();
+    let ref_count = published.ref_count();
+    assert_eq!(early.next().now_or_never(), None);
+    let s = ref_count.subscribe();
+    assert_eq!(s.collect::<Vec<_>>().await, vec![0, 1, 2]);
+    assert_eq!(early.collect::<Vec<_>>().await, vec![0, 1, 2]);
+}
+
+#[test]
+fn ref_count_disconnects_with_last_handle() {
+    let upstream = Arc::new(());
+    let in_stream = upstream.clone();
+    let made = Arc::new(AtomicUsize::new(0));
+    let counter = made.clone();
+    let ref_count = RefCount::new(move || {
+        counter.fetch_add(1, Ordering::SeqCst);
+        let in_stream = in_stream.clone();
+        source::of(0..).map(move |v| { let _ = &in_stream; v })
+    });
+    let mut s1 = ref_count.subscribe();
+    assert_eq!(s1.next().now_or_never(), Some(Some(0)));
+    let mut s2 = ref_count.subscribe();
+    assert_eq!(s2.next().now_or_never(), Some(Some(1)));
+    assert_eq!(made.load(Ordering::SeqCst), 1);
+    drop(s1);
+    assert_eq!(Arc::strong_count(&upstream), 3);
+    drop(s2);
+    assert_eq!(Arc::strong_count(&upstream), 2);
+    // Subscribing again makes a new upstream
+    let mut s3 = ref_count.subscribe();
+    assert_eq!(s3.next().now_or_never(), Some(Some(0)));
+    assert_eq!(made.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn published_ref_count_ends_after_disconnect() {
+    let ref_count = source::of(0..).publish().ref_count();
+    let mut s1 = ref_count.subscribe();
+    assert_eq!(s1.next().now_or_never(), Some(Some(0)));
+    drop(s1);
+    let s2 = ref_count.subscribe();
+    assert_eq!(s2.collect::<Vec<_>>().now_or_never(), Some(vec![]));
+}
+
+#[tokio::test]
+async fn partition_splits_on_predicate() {
+    let (even, odd) = source::of(0..6).partition(|v| v % 2 == 0);
+    assert_eq!(odd.collect::<Vec<_>>().await, vec![1, 3, 5]);
+    assert_eq!(even.collect::<Vec<_>>().await, vec![0, 2, 4]);
+}
+
+#[tokio::test]
+async fn partition_halves_on_different_tasks() {
+    let (even, odd) = source::interval(5).take(6).partition(|v| v % 2 == 0);
+    let other = tokio::spawn(odd.collect::<Vec<_>>());
+    assert_eq!(even.collect::<Vec<_>>().await, vec![0, 2, 4]);
+    assert_eq!(other.await.unwrap(), vec![1, 3, 5]);
+}
+
+#[test]
+fn partition_lagging_half_gets_queued_values() {
+    let scheduler = VirtualTimeScheduler::new();
+    let (small, large) = source::interval_with_scheduler(10, scheduler.clone())
+        .take(5)
+        .partition(|v| *v < 3);
+    assert_eq!(scheduler.run(large.collect::<Vec<_>>()), vec![3, 4]);
+    assert_eq!(small.collect::<Vec<_>>().now_or_never(), Some(vec![0, 1, 2]));
+}
+
+#[test]
+fn partition_returns_when_every_value_is_for_the_other_half() {
+    let (none, all) = futures::stream::iter(0..).partition(|v| *v < 0);
+    let mut none = Box::pin(none);
+    assert_eq!(none.next().now_or_never(), None);
+    assert_eq!(all.take(3).collect::<Vec<_>>().now_or_never(), Some(vec![0, 1, 2]));
+}
+
+#[test]
+fn partition_dropped_half_does_not_stop_the_other() {
+    let (even, odd) = source::of(0..6).partition(|v| v % 2 == 0);
+    drop(odd);
+    assert_eq!(even.collect::<Vec<_>>().now_or_never(), Some(vec![0, 2, 4]));
+}
@@ -0,0 +1,70 @@
+use rxstream::source;
+use rxstream::operators::RxStreamEx;
+use rxstream::scheduler::VirtualTimeScheduler;
+use futures::{FutureExt, StreamExt};
+use std::time::Duration;
+
+#[test]
+fn virtual_interval_only_moves_when_advanced() {
+    let scheduler = VirtualTimeScheduler::new();
+    let mut t = source::interval_with_scheduler(10, scheduler.clone());
+    assert_eq!(t.next().now_or_never(), None);
+    scheduler.advance_by(Duration::from_millis(9));
+    assert_eq!(t.next().now_or_never(), None);
+    scheduler.advance_by(Duration::from_millis(1));
+    assert_eq!(t.next().now_or_never(), Some(Some(0)));
+    assert_eq!(t.next().now_or_never(), None);
+}
+
+#[test]
+fn virtual_interval_catches_up_missed_ticks() {
+    let scheduler = VirtualTimeScheduler::new();
+    let mut t = source::interval_with_scheduler(10, scheduler.clone());
+    scheduler.advance_by(Duration::from_millis(35));
+    assert_eq!(t.next().now_or_never(), Some(Some(0)));
+    assert_eq!(t.next().now_or_never(), Some(Some(1)));
+    assert_eq!(t.next().now_or_never(), Some(Some(2)));
+    assert_eq!(t.next().now_or_never(), None);
+}
+
+#[test]
+fn virtual_run_jumps_to_each_deadline() {
+    let scheduler = VirtualTimeScheduler::new();
+    let r = scheduler.run(
+        source::timer_with_scheduler(3, 10, scheduler.clone()).take(3).collect::<Vec<_>>()
+    );
+    assert_eq!(r, vec![0, 1, 2]);
+    assert_eq!(scheduler.elapsed(), Duration::from_millis(23));
+}
+
+#[test]
+#[should_panic]
+fn virtual_run_panics_when_nothing_is_pending() {
+    let scheduler = VirtualTimeScheduler::new();
+    scheduler.run(futures::future::pending::<()>());
+}
+
+#[test]
+fn virtual_simple_time_buffer_emit_vecs_from_timer() {
+    let scheduler = VirtualTimeScheduler::new();
+    let f = scheduler.run(
+        source::interval_with_scheduler(31, scheduler.clone())
+            .buffer_time_with_scheduler(50, scheduler.clone())
+            .take(4)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(f, vec![vec![0], vec![1, 2], vec![3], vec![4, 5]]);
+    assert_eq!(scheduler.elapsed(), Duration::from_millis(200));
+}
+
+#[test]
+fn virtual_overlapped_time_buffer_creation_time_large_than_span() {
+    let scheduler = VirtualTimeScheduler::new();
+    let f = scheduler.run(
+        source::interval_with_scheduler(10, scheduler.clone())
+            .buffer_time_with_creation_interval_and_scheduler(35, 45, scheduler.clone())
+            .take(3)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(f, vec![[0, 1, 2], [5, 6, 7], [10, 11, 12]]);
+}
@@ -1,5 +1,5 @@
 use rxstream::source;
-use futures::StreamExt;
+use futures::{FutureExt, StreamExt};
 use futures::stream::select;
 mod common;
 
@@ -25,3 +25,49 @@ async fn timer_generates_timed_list() {
     let r = select(t1, t2).collect::<Vec<_>>().await;
     assert_eq!(r, vec![0, 0, 1, 1, 2, 2, 3, 4, 5])
 }
+
+#[tokio::test]
+async fn range_steps_to_end() {
+    let r = source::range(1, 10, 3).collect::<Vec<_>>().await;
+    assert_eq!(r, vec![1, 4, 7])
+}
+
+#[tokio::test]
+async fn throw_error_yields_error_then_ends() {
+    let r = source::throw_error::<i32, _>("boom").collect::<Vec<_>>().await;
+    assert_eq!(r, vec![Err("boom")])
+}
+
+#[tokio::test]
+async fn never_does_not_yield_nor_end() {
+    let mut s = select(source::never(), source::of(0..3));
+    let r = s.by_ref().take(3).collect::<Vec<_>>().await;
+    assert_eq!(r, vec![0, 1, 2]);
+    assert_eq!(s.next().now_or_never(), None)
+}
+
+#[tokio::test]
+async fn repeat_value_repeats() {
+    let r = source::repeat_value('a').take(3).collect::<String>().await;
+    assert_eq!(r, "aaa")
+}
+
+#[tokio::test]
+async fn defer_creates_stream_on_first_poll() {
+    let created = std::cell::Cell::new(false);
+    let s = source::defer(|| {
+        created.set(true);
+        source::of(0..2)
+    });
+    assert!(!created.get());
+    let r = s.collect::<Vec<_>>().await;
+    assert!(created.get());
+    assert_eq!(r, vec![0, 1])
+}
+
+#[tokio::test]
+async fn generate_loops_while_condition_holds() {
+    let r = source::generate(1, |s| *s < 20, |s| s * 2, |s| s.to_string())
+        .collect::<Vec<_>>().await;
+    assert_eq!(r, vec!["1", "2", "4", "8", "16"])
+}
@@ -0,0 +1,129 @@
+use rxstream::subject::{AsyncSubject, BehaviorSubject, ReplaySubject, Subject};
+use rxstream::operators::RxStreamEx;
+use futures::{FutureExt, SinkExt, StreamExt};
+
+#[tokio::test]
+async fn subject_fans_out_to_subscribers() {
+    let subject = Subject::new();
+    let s1 = subject.subscribe();
+    subject.next(1);
+    let s2 = subject.subscribe();
+    subject.next(2);
+    subject.complete();
+    subject.next(3);
+    assert_eq!(s1.collect::<Vec<_>>().await, vec![1, 2]);
+    assert_eq!(s2.collect::<Vec<_>>().await, vec![2]);
+}
+
+#[tokio::test]
+async fn subject_wakes_pending_subscriber() {
+    let subject = Subject::new();
+    let pusher = subject.clone();
+    let handle = tokio::spawn(subject.subscribe().pairwise().collect::<Vec<_>>());
+    tokio::task::yield_now().await;
+    for i in 0..3 {
+        pusher.next(i);
+        tokio::task::yield_now().await;
+    }
+    pusher.complete();
+    assert_eq!(handle.await.unwrap(), vec![(0, 1), (1, 2)]);
+}
+
+#[test]
+fn subject_subscriber_is_pending_until_pushed() {
+    let subject = Subject::<i32>::new();
+    let mut s = subject.subscribe();
+    assert_eq!(s.next().now_or_never(), None);
+    subject.next(1);
+    assert_eq!(s.next().now_or_never(), Some(Some(1)));
+}
+
+#[tokio::test]
+async fn subject_error_ends_subscriptions_and_reaches_late_subscribers() {
+    let subject = Subject::<Result<i32, &str>>::new();
+    let s1 = subject.subscribe();
+    subject.next(Ok(1));
+    subject.error("boom");
+    subject.next(Ok(2));
+    assert_eq!(s1.collect::<Vec<_>>().await, vec![Ok(1), Err("boom")]);
+    assert_eq!(subject.subscribe().collect::<Vec<_>>().await, vec![Err("boom")]);
+}
+
+#[tokio::test]
+async fn subject_keeps_pushing_after_a_subscription_is_dropped() {
+    let subject = Subject::new();
+    let s1 = subject.subscribe();
+    drop(subject.subscribe());
+    subject.next(1);
+    subject.complete();
+    assert_eq!(s1.collect::<Vec<_>>().await, vec![1]);
+}
+
+#[tokio::test]
+async fn subject_as_sink() {
+    let mut subject = Subject::new();
+    let s = subject.subscribe();
+    subject.send_all(&mut futures::stream::iter(0..3).map(Ok)).await.unwrap();
+    subject.close().await.unwrap();
+    assert_eq!(s.collect::<Vec<_>>().await, vec![0, 1, 2]);
+}
+
+#[tokio::test]
+async fn behavior_subject_starts_with_current_value() {
+    let subject = BehaviorSubject::new(0);
+    let s1 = subject.subscribe();
+    subject.next(1);
+    subject.next(2);
+    assert_eq!(subject.value(), Some(2));
+    let s2 = subject.subscribe();
+    subject.next(3);
+    subject.complete();
+    assert_eq!(s1.collect::<Vec<_>>().await, vec![0, 1, 2, 3]);
+    assert_eq!(s2.collect::<Vec<_>>().await, vec![2, 3]);
+    assert_eq!(subject.value(), None);
+    assert_eq!(subject.subscribe().collect::<Vec<_>>().await, vec![]);
+}
+
+#[tokio::test]
+async fn replay_subject_replays_last_values() {
+    let subject = ReplaySubject::new(2);
+    for i in 0..4 {
+        subject.next(i);
+    }
+    let s1 = subject.subscribe();
+    subject.next(4);
+    subject.complete();
+    assert_eq!(s1.collect::<Vec<_>>().await, vec![2, 3, 4]);
+    assert_eq!(subject.subscribe().collect::<Vec<_>>().await, vec![3, 4]);
+}
+
+#[tokio::test]
+async fn replay_subject_replays_before_error() {
+    let subject = ReplaySubject::<Result<i32, &str>>::unbounded();
+    subject.next(Ok(1));
+    subject.next(Ok(2));
+    subject.error("boom");
+    assert_eq!(subject.subscribe().collect::<Vec<_>>().await, vec![Ok(1), Ok(2), Err("boom")]);
+}
+
+#[tokio::test]
+async fn async_subject_yields_last_value_on_complete() {
+    let subject = AsyncSubject::new();
+    let mut s1 = subject.subscribe();
+    subject.next(1);
+    subject.next(2);
+    assert_eq!(s1.next().now_or_never(), None);
+    subject.complete();
+    assert_eq!(s1.collect::<Vec<_>>().await, vec![2]);
+    assert_eq!(subject.subscribe().collect::<Vec<_>>().await, vec![2]);
+}
+
+#[tokio::test]
+async fn async_subject_yields_only_error() {
+    let subject = AsyncSubject::<Result<i32, &str>>::new();
+    let s1 = subject.subscribe();
+    subject.next(Ok(1));
+    subject.error("boom");
+    assert_eq!(s1.collect::<Vec<_>>().await, vec![Err("boom")]);
+    assert_eq!(subject.subscribe().collect::<Vec<_>>().await, vec![Err("boom")]);
+}
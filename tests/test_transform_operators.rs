@@ -1,6 +1,12 @@
 use rxstream::source;
-use rxstream::operators::RxStreamEx;
-use futures::StreamExt;
+use rxstream::operators::{RxStreamEx, SimpleTimeBufferredStream, TimeSpan};
+use rxstream::testing::TestScheduler;
+use rxstream::scheduler::VirtualTimeScheduler;
+use futures::{FutureExt, StreamExt};
+use futures::stream::LocalBoxStream;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
 
 
 #[tokio::test]
@@ -70,7 +76,7 @@ async fn ovlapped_time_buffer_creation_time_large_than_span() {
             35, 
             45
         ).take(3).collect::<Vec<_>>().await;
-    assert_eq!(f, vec![[0, 1, 2], [5, 6, 7], [10, 11, 12]])
+    assert_ticks_near(&f, &[0, 5, 10]);
 }
 
 #[tokio::test]
@@ -80,5 +86,412 @@ async fn ovlapped_time_buffer_creation_time_smaller_than_span() {
             35, /* time_span */
             15  /* creation_interval*/
         ).take(3).collect::<Vec<_>>().await;
+    assert_ticks_near(&f, &[0, 2, 4]);
+}
+
+// Buffers open on the same milliseconds as the ticks, so on the wall clock a
+// buffer may start a tick early or late, the exact buffers are checked on
+// virtual time
+fn assert_ticks_near(buffers: &[Vec<u64>], starts: &[u64]) {
+    assert_eq!(buffers.len(), starts.len(), "{:?}", buffers);
+    for (buffer, start) in buffers.iter().zip(starts) {
+        assert!((2..=4).contains(&buffer.len()), "{:?}", buffers);
+        assert!(buffer[0].abs_diff(*start) <= 1, "{:?}", buffers);
+        assert!(buffer.windows(2).all(|w| w[1] == w[0] + 1), "{:?}", buffers);
+    }
+}
+
+// Buffers open and close on the same milliseconds as the ticks, which races
+// the wall clock, virtual time can't
+#[test]
+fn ovlapped_time_buffer_creation_time_large_than_span_on_virtual_time() {
+    let scheduler = VirtualTimeScheduler::new();
+    let f = scheduler.run(source::interval_with_scheduler(10, scheduler.clone())
+        .buffer_time_with_creation_interval_and_scheduler(
+            35, 
+            45,
+            scheduler.clone()
+        ).take(3).collect::<Vec<_>>());
+    assert_eq!(f, vec![[0, 1, 2], [5, 6, 7], [10, 11, 12]])
+}
+
+#[test]
+fn ovlapped_time_buffer_creation_time_smaller_than_span_on_virtual_time() {
+    let scheduler = VirtualTimeScheduler::new();
+    let f = scheduler.run(source::interval_with_scheduler(10, scheduler.clone())
+        .buffer_time_with_creation_interval_and_scheduler(
+            35, /* time_span */
+            15, /* creation_interval*/
+            scheduler.clone()
+        ).take(3).collect::<Vec<_>>());
     assert_eq!(f, vec![[0, 1, 2], [2, 3, 4], [4, 5, 6]])
-}
\ No newline at end of file
+}
+// Time based streams are plain types, so they can be named and stored without boxing
+struct Ticks {
+    buffered: SimpleTimeBufferredStream<source::TimerStream<VirtualTimeScheduler>, VirtualTimeScheduler>,
+}
+
+fn buffered_ticks(period: u64, time_span: u64, scheduler: VirtualTimeScheduler) -> Ticks {
+    Ticks {
+        buffered: source::interval_with_scheduler(period, scheduler.clone())
+            .buffer_time_with_scheduler(time_span, scheduler),
+    }
+}
+
+#[test]
+fn simple_time_buffer_can_be_named() {
+    let scheduler = VirtualTimeScheduler::new();
+    let ticks = buffered_ticks(31, 50, scheduler.clone());
+    let f = scheduler.run(ticks.buffered.take(2).collect::<Vec<_>>());
+    assert_eq!(f, vec![vec![0], vec![1, 2]])
+}
+
+#[test]
+fn simple_external_time_buffer_emit_vecs_on_given_timer() {
+    let ts = TestScheduler::new();
+    let mut timer = source::interval_with_scheduler(3, ts.clone());
+    let s = ts.cold("-a-b-c---|", &[('a', 1), ('b', 2), ('c', 3)])
+        .buffer_time_with_external_timer(Pin::new(&mut timer));
+    ts.expect(s, "---x--y--(z|)", &[('x', vec![1]), ('y', vec![2, 3]), ('z', vec![])]);
+}
+
+#[test]
+fn buffer_emits_vecs_on_notifier() {
+    let ts = TestScheduler::new();
+    let notifier = ts.cold("--x---x-----", &[('x', ())]);
+    let s = ts.cold("-a-b-c---|", &[('a', 1), ('b', 2), ('c', 3)]).buffer(notifier);
+    ts.expect(s, "--x---y--(z|)", &[('x', vec![1]), ('y', vec![2, 3]), ('z', vec![])]);
+}
+
+#[test]
+fn buffer_emits_rest_when_notifier_ended() {
+    let ts = TestScheduler::new();
+    let notifier = ts.cold("--x|", &[('x', ())]);
+    let s = ts.cold("-a-b-c|", &[('a', 1), ('b', 2), ('c', 3)]).buffer(notifier);
+    ts.expect(s, "--x---(y|)", &[('x', vec![1]), ('y', vec![2, 3])]);
+}
+
+#[test]
+fn buffer_when_restarts_closing_stream() {
+    let ts = TestScheduler::new();
+    let closing = ts.clone();
+    let s = ts.cold("-a-b---c--|", &[('a', 1), ('b', 2), ('c', 3)])
+        .buffer_when(move || closing.cold("----x", &[('x', ())]));
+    ts.expect(s, "----x---y-(z|)", &[('x', vec![1, 2]), ('y', vec![3]), ('z', vec![])]);
+}
+
+#[test]
+fn buffer_toggle_closes_buffers_in_any_order() {
+    let ts = TestScheduler::new();
+    let closing = ts.clone();
+    let openings = ts.cold("-o-p", &[('o', 5), ('p', 2)]);
+    let s = ts.cold("a-b-c---e-|", &[('a', 1), ('b', 2), ('c', 3), ('e', 5)])
+        .buffer_toggle(openings, move |n: i32| closing.cold(&format!("{}x", "-".repeat(n as usize)), &[('x', ())]));
+    ts.expect(s, "-----yx---|", &[('x', vec![2, 3]), ('y', vec![3])]);
+}
+
+#[test]
+fn buffer_toggle_returns_with_always_ready_openings() {
+    let mut s = futures::stream::pending::<i32>()
+        .buffer_toggle(futures::stream::repeat(()), |_| futures::stream::pending::<()>());
+    assert!(s.next().now_or_never().is_none());
+    let s = futures::stream::pending::<i32>()
+        .window_toggle(futures::stream::repeat(()), |_| futures::stream::pending::<()>());
+    assert_eq!(futures::executor::block_on(s.take(2).count()), 2);
+}
+
+#[test]
+fn buffer_toggle_emits_open_buffers_on_end() {
+    let ts = TestScheduler::new();
+    let closing = ts.clone();
+    let openings = ts.cold("-o", &[('o', ())]);
+    let s = ts.cold("a-b-|", &[('a', 1), ('b', 2)])
+        .buffer_toggle(openings, move |_| closing.cold::<()>("-", &[]));
+    ts.expect(s, "----(x|)", &[('x', vec![2])]);
+}
+
+// Each window collected, yielded when it closes
+fn collected(w: impl futures::Stream<Item = i32>) -> impl futures::Stream<Item = Vec<i32>> {
+    w.collect::<Vec<_>>().into_stream()
+}
+
+#[test]
+fn window_count_opens_next_window_after_count() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    ts.expect(ts.cold("-a-b-c-|", &v).window_count(2).map(|_| 'w'), "w--w---|", &[('w', 'w')]);
+    let s = ts.cold("-a-b-c-|", &v).window_count(2).merge_map(collected);
+    ts.expect(s, "---x---(y|)", &[('x', vec![1, 2]), ('y', vec![3])]);
+}
+
+#[tokio::test]
+async fn window_count_windows_can_be_read_one_at_a_time() {
+    let r = source::of(vec![1, 2, 3, 4, 5])
+        .window_count(2)
+        .then(|w| w.collect::<Vec<_>>())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(r, vec![vec![1, 2], vec![3, 4], vec![5]]);
+}
+
+#[test]
+fn windows_can_be_concatenated() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let s = ts.cold("-a-b-c---|", &v).window_time_with_scheduler(3, ts.clone()).concat_map(|w| w);
+    ts.expect(s, "-a-b-c---|", &v);
+    let s = ts.cold("-a-b-c-|", &v).window_count_with_skip(2, 1).concat_map(collected);
+    ts.expect(s, "---x-y-(ze|)", &[('x', vec![1, 2]), ('y', vec![2, 3]), ('z', vec![3]), ('e', vec![])]);
+}
+
+#[test]
+fn window_count_with_skip_overlaps_windows() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b-c|", &[('a', 1), ('b', 2), ('c', 3)])
+        .window_count_with_skip(2, 1)
+        .merge_map(collected)
+        .take(2);
+    ts.expect(s, "---x-(y|)", &[('x', vec![1, 2]), ('y', vec![2, 3])]);
+}
+
+#[test]
+fn window_time_yields_values_as_they_come() {
+    let ts = TestScheduler::new();
+    let v = [('a', 1), ('b', 2), ('c', 3)];
+    let s = ts.cold("-a-b-c---|", &v).window_time_with_scheduler(3, ts.clone()).merge_map(|w| w);
+    ts.expect(s, "-a-b-c---|", &v);
+    let s = ts.cold("-a-b-c---|", &v).window_time_with_scheduler(3, ts.clone()).merge_map(collected);
+    ts.expect(s, "---x--y--(zz|)", &[('x', vec![1]), ('y', vec![2, 3]), ('z', vec![])]);
+}
+
+#[test]
+fn window_time_with_creation_interval_overlaps_windows() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b-c-d|", &[('a', 1), ('b', 2), ('c', 3), ('d', 4)])
+        .window_time_with_creation_interval_and_scheduler(4, 2, ts.clone())
+        .merge_map(collected)
+        .take(2);
+    ts.expect(s, "----x--(y|)", &[('x', vec![1, 2]), ('y', vec![3])]);
+}
+
+#[test]
+fn window_closes_windows_on_notifier() {
+    let ts = TestScheduler::new();
+    let notifier = ts.cold("--x---x-----", &[('x', ())]);
+    let s = ts.cold("-a-b-c---|", &[('a', 1), ('b', 2), ('c', 3)]).window(notifier).merge_map(collected);
+    ts.expect(s, "--x---y--(z|)", &[('x', vec![1]), ('y', vec![2, 3]), ('z', vec![])]);
+}
+
+#[test]
+fn window_toggle_closes_windows_in_any_order() {
+    let ts = TestScheduler::new();
+    let closing = ts.clone();
+    let openings = ts.cold("-o-p", &[('o', 5), ('p', 2)]);
+    let s = ts.cold("a-b-c---e-|", &[('a', 1), ('b', 2), ('c', 3), ('e', 5)])
+        .window_toggle(openings, move |n: i32| closing.cold(&format!("{}x", "-".repeat(n as usize)), &[('x', ())]))
+        .merge_map(collected);
+    ts.expect(s, "-----yx---|", &[('x', vec![2, 3]), ('y', vec![3])]);
+}
+
+// Inner streams yielding v*10+1 and v*10+2 for the outer value v
+fn inner_of(ts: &TestScheduler, marble: &'static str) -> impl FnMut(i32) -> LocalBoxStream<'static, i32> {
+    let ts = ts.clone();
+    move |v| ts.cold(marble, &[('x', 1), ('y', 2)]).map(move |i| v * 10 + i).boxed_local()
+}
+
+#[test]
+fn switch_map_drops_previous_inner() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b----|", &[('a', 1), ('b', 2)]).switch_map(inner_of(&ts, "-x--y|"));
+    ts.expect(s, "--p-q--r|", &[('p', 11), ('q', 21), ('r', 22)]);
+}
+
+#[tokio::test]
+async fn switch_map_polls_each_inner_of_a_synchronous_outer() {
+    let s = source::of(vec![1, 2, 3]).switch_map(|v| source::of(vec![v * 10, v * 10 + 1]));
+    assert_eq!(s.collect::<Vec<_>>().await, vec![10, 20, 30, 31]);
+}
+
+#[test]
+fn switch_map_returns_with_an_always_ready_outer() {
+    let mut s = futures::stream::repeat(1).switch_map(|_| futures::stream::pending::<i32>());
+    assert_eq!(s.next().now_or_never(), None);
+}
+
+#[test]
+fn merge_map_runs_inners_together() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b|", &[('a', 1), ('b', 2)]).merge_map(inner_of(&ts, "-x--y|"));
+    ts.expect(s, "--p-qr-s|", &[('p', 11), ('q', 21), ('r', 12), ('s', 22)]);
+}
+
+#[test]
+fn merge_map_with_concurrency_waits_for_room() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("abc|", &[('a', 1), ('b', 2), ('c', 3)])
+        .merge_map_with_concurrency(inner_of(&ts, "-x|"), 2);
+    ts.expect(s, "-pqr|", &[('p', 11), ('q', 21), ('r', 31)]);
+}
+
+#[test]
+fn concat_map_runs_inners_one_after_another() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b|", &[('a', 1), ('b', 2)]).concat_map(inner_of(&ts, "-x--y|"));
+    ts.expect(s, "--p--q-r--s|", &[('p', 11), ('q', 12), ('r', 21), ('s', 22)]);
+}
+
+#[test]
+fn exhaust_map_ignores_values_while_busy() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b---c|", &[('a', 1), ('b', 2), ('c', 3)]).exhaust_map(inner_of(&ts, "-x--y|"));
+    ts.expect(s, "--p--q--r--s|", &[('p', 11), ('q', 12), ('r', 31), ('s', 32)]);
+}
+
+#[test]
+fn scan_yields_each_accumulator() {
+    let ts = TestScheduler::new();
+    let s = RxStreamEx::scan(ts.cold("-a-b-c|", &[('a', 1), ('b', 2), ('c', 3)]), 0, |acc, v| acc + v);
+    ts.expect(s, "-a-b-c|", &[('a', 1), ('b', 3), ('c', 6)]);
+}
+
+#[test]
+fn scan_seedless_starts_from_first_value() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b-c|", &[('a', 2), ('b', 3), ('c', 4)]).scan_seedless(|acc, v| acc * v);
+    ts.expect(s, "-a-b-c|", &[('a', 2), ('b', 6), ('c', 24)]);
+}
+
+#[test]
+fn reduce_yields_accumulator_on_end() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b-c|", &[('a', 1), ('b', 2), ('c', 3)]).reduce(0, |acc, v| acc + v);
+    ts.expect(s, "------(r|)", &[('r', 6)]);
+}
+
+#[test]
+fn reduce_of_empty_stream_yields_seed() {
+    let ts = TestScheduler::new();
+    let s = ts.cold::<i32>("---|", &[]).reduce(10, |acc, v| acc + v);
+    ts.expect(s, "---(r|)", &[('r', 10)]);
+}
+
+#[test]
+fn reduce_seedless_of_empty_stream_yields_nothing() {
+    let ts = TestScheduler::new();
+    let s = ts.cold::<i32>("---|", &[]).reduce_seedless(|acc, v| acc + v);
+    ts.expect(s, "---|", &[]);
+    let s = ts.cold("-a-b|", &[('a', 2), ('b', 3)]).reduce_seedless(|acc, v| acc * v);
+    ts.expect(s, "----(r|)", &[('r', 6)]);
+}
+
+// Inner stream yielding acc + v once, after marble
+fn add_after(ts: &TestScheduler, marble: &'static str) -> impl FnMut(i32, i32) -> LocalBoxStream<'static, i32> {
+    let ts = ts.clone();
+    move |acc, v| ts.cold(marble, &[('x', 0)]).map(move |_| acc + v).boxed_local()
+}
+
+#[test]
+fn merge_scan_feeds_inner_values_back() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a---b|", &[('a', 1), ('b', 2)]).merge_scan(0, add_after(&ts, "-x|"));
+    ts.expect(s, "--p---q|", &[('p', 1), ('q', 3)]);
+}
+
+#[test]
+fn merge_scan_with_concurrency_maps_with_latest_accumulator() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("ab|", &[('a', 1), ('b', 2)])
+        .merge_scan_with_concurrency(0, add_after(&ts, "-x|"), 1);
+    ts.expect(s, "-p-q|", &[('p', 1), ('q', 3)]);
+}
+
+// Inner stream yielding v + 1 after marble while the last digit is below 3
+fn increment_after(ts: &TestScheduler, marble: &'static str) -> impl FnMut(i32) -> LocalBoxStream<'static, i32> {
+    let ts = ts.clone();
+    move |v| {
+        let marble = if v % 10 < 3 { marble } else { "|" };
+        ts.cold(marble, &[('x', 0)]).map(move |_| v + 1).boxed_local()
+    }
+}
+
+#[test]
+fn expand_maps_values_recursively() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("a|", &[('a', 1)]).expand(increment_after(&ts, "-x|"));
+    ts.expect(s, "abc|", &[('a', 1), ('b', 2), ('c', 3)]);
+}
+
+#[test]
+fn expand_with_concurrency_queues_values() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("ab|", &[('a', 1), ('b', 11)])
+        .expand_with_concurrency(increment_after(&ts, "--x|"), 1);
+    ts.expect(s, "abp--q--r--s|", &[('a', 1), ('b', 11), ('p', 2), ('q', 12), ('r', 3), ('s', 13)]);
+}
+
+#[test]
+fn group_by_yields_a_stream_per_key() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b-c-d|", &[('a', 1), ('b', 2), ('c', 3), ('d', 4)])
+        .group_by(|v| v % 2)
+        .merge_map(|g| {
+            let key = *g.key();
+            g.map(move |v| key * 10 + v)
+        });
+    ts.expect(s, "-a-b-c-d|", &[('a', 11), ('b', 2), ('c', 13), ('d', 4)]);
+}
+
+#[test]
+fn group_by_with_element_maps_values() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-a-b|", &[('a', 1), ('b', 2)])
+        .group_by_with_element(|v| v % 2, |v| v * 100)
+        .merge_map(|g| g);
+    ts.expect(s, "-a-b|", &[('a', 100), ('b', 200)]);
+}
+
+#[test]
+fn group_by_with_duration_closes_idle_groups() {
+    let ts = TestScheduler::new();
+    let s = ts.cold("-ab----c|", &[('a', 1), ('b', 2), ('c', 3)])
+        .group_by_with_duration(|_| 0, TimeSpan::new_with_scheduler(3, ts.clone()))
+        .merge_map(|g| futures::stream::once(g.collect::<Vec<_>>()));
+    ts.expect(s, "-----x--(y|)", &[('x', vec![1, 2]), ('y', vec![3])]);
+}
+
+#[test]
+fn group_by_waits_for_groups_to_take_their_values() {
+    let polled = Arc::new(AtomicUsize::new(0));
+    let counter = polled.clone();
+    let mut groups = source::of(0..)
+        .inspect(move |_| { counter.fetch_add(1, Ordering::SeqCst); })
+        .group_by(|v| v % 2);
+    let mut even = groups.next().now_or_never().unwrap().unwrap();
+    assert!(groups.next().now_or_never().is_none());
+    assert_eq!(polled.load(Ordering::SeqCst), 1);
+    assert_eq!(even.next().now_or_never(), Some(Some(0)));
+    let odd = groups.next().now_or_never().unwrap().unwrap();
+    assert_eq!(*odd.key(), 1);
+    assert_eq!(polled.load(Ordering::SeqCst), 2);
+}
+
+#[test]
+fn group_by_reopens_dropped_groups() {
+    let mut groups = source::of(0..).group_by(|v| v % 2);
+    let even = groups.next().now_or_never().unwrap().unwrap();
+    drop(even);
+    let mut odd = groups.next().now_or_never().unwrap().unwrap();
+    assert_eq!(odd.next().now_or_never(), Some(Some(1)));
+    let even = groups.next().now_or_never().unwrap().unwrap();
+    assert_eq!(*even.key(), 0);
+}
+
+#[test]
+fn group_by_takes_local_streams_and_selectors() {
+    let offset = std::rc::Rc::new(100);
+    let values = [1, 2, 3];
+    let s = source::of(values.iter())
+        .group_by_with_element(|v| **v % 2, move |v| *offset + v)
+        .merge_map(|g| g);
+    assert_eq!(s.collect::<Vec<_>>().now_or_never(), Some(vec![101, 102, 103]));
+}